@@ -0,0 +1,117 @@
+//! Timings for the distance functions; run with `cargo bench`
+//!
+//! Cases are grouped by what they exercise, and a filter may be passed to run only the
+//! matching ones, e.g., `cargo bench -- unicode`.
+
+use std::hint::black_box;
+
+use agnostic_levenshtein::{edit_distance, edit_distance_batch, edit_distance_slice};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const ASCII_WORDS: [&str; 6] = [
+    "kitten", "sitting", "mitten", "kitchen", "bitten", "written",
+];
+const UNICODE_WORDS: [&str; 6] = ["maḥmūd", "muḥammad", "ʿalī", "ʿumar", "شاهنامه", "شهنامه"];
+
+/// Input-size buckets, for each mode: within one word (Myers' algorithm, for ASCII), a
+/// few words, a paragraph, and a page
+fn sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("size");
+
+    for (mode, words, ascii) in [
+        ("ascii", ASCII_WORDS, true),
+        ("unicode", UNICODE_WORDS, false),
+    ] {
+        for (bucket, repeat) in [
+            ("short", 1),
+            ("medium", 4),
+            ("long", 40),
+            ("very long", 100),
+        ] {
+            let a = words.join(" ").repeat(repeat);
+            let words_rev: Vec<&str> = words.iter().rev().copied().collect();
+            let b = words_rev.join(" ").repeat(repeat);
+            group.bench_with_input(BenchmarkId::new(bucket, mode), &(a, b), |bench, (a, b)| {
+                bench.iter(|| edit_distance(black_box(a), black_box(b), ascii));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// One query against a word list
+fn batch(c: &mut Criterion) {
+    let list: Vec<String> = ASCII_WORDS
+        .iter()
+        .chain(&UNICODE_WORDS)
+        .cycle()
+        .take(1000)
+        .enumerate()
+        .map(|(i, w)| format!("{w}{}", i % 7))
+        .collect();
+    let list: Vec<&str> = list.iter().map(String::as_str).collect();
+
+    let mut group = c.benchmark_group("batch of 1000 words");
+    for (mode, ascii) in [("ascii", true), ("unicode", false)] {
+        group.bench_function(mode, |bench| {
+            bench.iter(|| edit_distance_batch(black_box("kitchens"), black_box(&list), ascii));
+        });
+    }
+    group.finish();
+}
+
+/// Long strings, with and without shared affixes to trim
+fn affixes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("affixes");
+
+    let affix = "the quick brown fox jumps over the lazy dog ".repeat(50);
+    let a = format!("{affix}kitten{affix}");
+    let b = format!("{affix}sitting{affix}");
+    for (mode, ascii) in [("ascii", true), ("unicode", false)] {
+        group.bench_function(BenchmarkId::new("shared", mode), |bench| {
+            bench.iter(|| edit_distance(black_box(&a), black_box(&b), ascii));
+        });
+    }
+
+    let a = "Abū al-Qāsim Firdawsī Ṭūsī ".repeat(40);
+    let b = "Abū Manṣūr Muḥammad ibn ʿAbd al-Razzāq ".repeat(30);
+    group.bench_function(BenchmarkId::new("none shared", "unicode"), |bench| {
+        bench.iter(|| edit_distance(black_box(&a), black_box(&b), false));
+    });
+
+    let a = "ACGTTGCAAGCTTACG".repeat(64);
+    let b = "TGCAACGTACGATCGA".repeat(60);
+    group.bench_function(BenchmarkId::new("none shared", "ascii"), |bench| {
+        bench.iter(|| edit_distance(black_box(&a), black_box(&b), true));
+    });
+
+    group.finish();
+}
+
+/// Paths that don't trim affixes up front, so they rely on skipping a shared prefix
+fn shared_prefix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("shared prefix");
+
+    let prefix = "the quick brown fox jumps over the lazy dog ".repeat(20);
+    let a: Vec<char> = format!("{prefix}kitten").chars().collect();
+    let b: Vec<char> = format!("{prefix}sitting").chars().collect();
+    group.bench_function("slice", |bench| {
+        bench.iter(|| edit_distance_slice(black_box(&a), black_box(&b)));
+    });
+
+    let query = format!("{prefix}kitten");
+    let candidates: Vec<String> = ["sitting", "mitten", "kitchen"]
+        .iter()
+        .map(|w| format!("{prefix}{w}"))
+        .collect();
+    let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+    group.bench_function("unicode batch of 3", |bench| {
+        bench.iter(|| edit_distance_batch(black_box(&query), black_box(&candidates), false));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, sizes, batch, affixes, shared_prefix);
+criterion_main!(benches);
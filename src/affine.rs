@@ -0,0 +1,154 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+// Stands in for impossible states; all costs are added with saturation, so it stays put
+const INF: u32 = u32::MAX;
+
+/// Returns the edit distance (`u32`) between two strings, `a` and `b`, with affine gap
+/// costs. The `ascii` flag works as for `edit_distance`.
+///
+/// A run of `k` consecutive insertions (or deletions) costs `gap_open` for the first and
+/// `gap_extend` for each of the rest, so long gaps can be made cheaper than scattered
+/// ones, as in sequence alignment. Substitutions cost 1. This is computed using Gotoh's
+/// algorithm, which tracks whether each alignment ends in a match or substitution, an
+/// insertion, or a deletion. With `gap_open == gap_extend == 1`, it reduces to the
+/// standard Levenshtein distance. Costs saturate at `u32::MAX`.
+#[must_use]
+pub fn affine_distance(a: &str, b: &str, gap_open: u32, gap_extend: u32, ascii: bool) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let gaps = Gaps {
+        open: gap_open,
+        extend: gap_extend,
+    };
+
+    if ascii {
+        gotoh(a.as_bytes(), b.as_bytes(), gaps)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        gotoh(&a_chars, &b_chars, gaps)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Gaps {
+    open: u32,
+    extend: u32,
+}
+
+impl Gaps {
+    /// Cost of a gap of `len` elements
+    const fn cost(self, len: usize) -> u32 {
+        if len == 0 {
+            0
+        } else {
+            self.open
+                .saturating_add((len as u32 - 1).saturating_mul(self.extend))
+        }
+    }
+}
+
+fn gotoh<T: PartialEq>(a: &[T], b: &[T], gaps: Gaps) -> u32 {
+    let m = a.len();
+
+    // Best costs of aligning prefixes, ending respectively in a match or substitution, a
+    // deletion from `a`, or an insertion from `b`
+    let mut sub_prev: Vec<u32> = vec![INF; m + 1];
+    let mut del_prev: Vec<u32> = (0..=m).map(|j| gaps.cost(j)).collect();
+    let mut ins_prev: Vec<u32> = vec![INF; m + 1];
+    sub_prev[0] = 0;
+    del_prev[0] = INF;
+
+    let mut sub_curr: Vec<u32> = vec![INF; m + 1];
+    let mut del_curr: Vec<u32> = vec![INF; m + 1];
+    let mut ins_curr: Vec<u32> = vec![INF; m + 1];
+
+    for (i, b_char) in b.iter().enumerate() {
+        sub_curr[0] = INF;
+        del_curr[0] = INF;
+        ins_curr[0] = gaps.cost(i + 1);
+
+        for j in 1..=m {
+            let cost = u32::from(a[j - 1] != *b_char);
+            sub_curr[j] = sub_prev[j - 1]
+                .min(del_prev[j - 1])
+                .min(ins_prev[j - 1])
+                .saturating_add(cost);
+
+            del_curr[j] = (sub_curr[j - 1].min(ins_curr[j - 1]))
+                .saturating_add(gaps.open)
+                .min(del_curr[j - 1].saturating_add(gaps.extend));
+
+            ins_curr[j] = (sub_prev[j].min(del_prev[j]))
+                .saturating_add(gaps.open)
+                .min(ins_prev[j].saturating_add(gaps.extend));
+        }
+
+        swap(&mut sub_prev, &mut sub_curr);
+        swap(&mut del_prev, &mut del_curr);
+        swap(&mut ins_prev, &mut ins_curr);
+    }
+
+    sub_prev[m].min(del_prev[m]).min(ins_prev[m])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn unit_gaps_match_levenshtein() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("شاهنامه", "شهنامه"),
+            ("", "maḥmūd"),
+            ("maḥmūd", ""),
+        ];
+
+        for (a, b) in pairs {
+            for ascii in [true, false] {
+                assert_eq!(
+                    affine_distance(a, b, 1, 1, ascii),
+                    edit_distance(a, b, ascii)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn long_gap_cheaper() {
+        // One deletion of four characters
+        assert_eq!(affine_distance("ACGTTTTACG", "ACGACG", 3, 1, true), 6);
+        // One deletion of four and a substitution beat two separate deletions of two
+        assert_eq!(affine_distance("ACTTGTTACG", "ACGACG", 3, 1, true), 7);
+        assert_eq!(edit_distance("ACTTGTTACG", "ACGACG", true), 4);
+    }
+
+    #[test]
+    fn gaps_at_the_ends() {
+        assert_eq!(affine_distance("", "abc", 5, 2, true), 9);
+        assert_eq!(affine_distance("abc", "", 5, 2, false), 9);
+        assert_eq!(affine_distance("abcxyz", "xyz", 5, 2, true), 9);
+    }
+
+    #[test]
+    fn extreme_gap_costs() {
+        assert_eq!(affine_distance("", "abc", 1, u32::MAX, true), u32::MAX);
+        assert_eq!(affine_distance("abc", "", u32::MAX, 1, false), u32::MAX);
+        assert_eq!(
+            affine_distance("abc", "", u32::MAX - 3, 1, true),
+            u32::MAX - 1
+        );
+        assert_eq!(
+            affine_distance("maḥmūd", "muḥmad", u32::MAX, u32::MAX, false),
+            2
+        );
+        assert_eq!(affine_distance("abcd", "ad", u32::MAX, 0, true), u32::MAX);
+    }
+}
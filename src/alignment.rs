@@ -0,0 +1,124 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+/// Returns the Needleman-Wunsch global alignment score of two strings, `a` and `b`. The
+/// `ascii` flag works as for `edit_distance`.
+///
+/// This is the best total over all alignments of the two strings, in which each pair of
+/// matching characters adds `match_bonus`, each pair of different ones subtracts
+/// `mismatch_penalty`, and each character aligned with a gap subtracts `gap_penalty`. So
+/// unlike a distance, higher is better, and long matching runs count in a pair's favor;
+/// the score is negative if the strings have little in common. The penalties are
+/// magnitudes, to be given as positive numbers. With a bonus of 0 and both penalties 1,
+/// the score is the negated Levenshtein distance.
+///
+/// The DP is that of `edit_distance`, maximizing rather than minimizing. Scores are
+/// added as `i32` without overflow checks in release builds, so very long strings with
+/// large parameters may overflow.
+#[must_use]
+pub fn alignment_score(
+    a: &str,
+    b: &str,
+    match_bonus: i32,
+    mismatch_penalty: i32,
+    gap_penalty: i32,
+    ascii: bool,
+) -> i32 {
+    let scoring = Scoring {
+        match_bonus,
+        mismatch_penalty,
+        gap_penalty,
+    };
+
+    if ascii {
+        needleman_wunsch(a.as_bytes(), b.as_bytes(), scoring)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        needleman_wunsch(&a_chars, &b_chars, scoring)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Scoring {
+    match_bonus: i32,
+    mismatch_penalty: i32,
+    gap_penalty: i32,
+}
+
+fn needleman_wunsch<T: PartialEq>(a: &[T], b: &[T], scoring: Scoring) -> i32 {
+    let m = a.len();
+
+    // Aligning each prefix of `a` with nothing but gaps
+    let mut dp_prev: Vec<i32> = Vec::with_capacity(m + 1);
+    dp_prev.push(0);
+    for j in 0..m {
+        dp_prev.push(dp_prev[j] - scoring.gap_penalty);
+    }
+    let mut dp_curr: Vec<i32> = vec![0; m + 1];
+
+    for b_elem in b {
+        dp_curr[0] = dp_prev[0] - scoring.gap_penalty;
+
+        for j in 1..=m {
+            let pair = if a[j - 1] == *b_elem {
+                scoring.match_bonus
+            } else {
+                -scoring.mismatch_penalty
+            };
+
+            let diagonal = dp_prev[j - 1] + pair;
+            let gap_in_a = dp_prev[j] - scoring.gap_penalty;
+            let gap_in_b = dp_curr[j - 1] - scoring.gap_penalty;
+
+            dp_curr[j] = diagonal.max(gap_in_a).max(gap_in_b);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn textbook_example() {
+        // The usual example, with +1 for a match and -1 for a mismatch or gap: e.g.,
+        // GCATG-CU against G-ATTACA, with four matches, two mismatches, and two gaps
+        assert_eq!(alignment_score("GCATGCU", "GATTACA", 1, 1, 1, true), 0);
+        // Four matches ("ittn"), two mismatches, and one gap
+        assert_eq!(alignment_score("kitten", "sitting", 1, 1, 1, false), 1);
+    }
+
+    #[test]
+    fn identical_and_empty() {
+        assert_eq!(alignment_score("maḥmūd", "maḥmūd", 2, 1, 1, false), 12);
+        assert_eq!(alignment_score("maḥmūd", "maḥmūd", 2, 1, 1, true), 18);
+        assert_eq!(alignment_score("", "kitten", 2, 1, 3, true), -18);
+        assert_eq!(alignment_score("", "", 2, 1, 3, true), 0);
+    }
+
+    #[test]
+    fn negated_distance() {
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("شاهنامه", ""),
+        ] {
+            let dist = i32::try_from(edit_distance(a, b, false)).unwrap();
+            assert_eq!(alignment_score(a, b, 0, 1, 1, false), -dist);
+        }
+    }
+
+    #[test]
+    fn gaps_preferred_when_cheap() {
+        // A mismatch costs more than a gap on each side
+        assert_eq!(alignment_score("ab", "cb", 1, 3, 1, true), -1);
+        assert_eq!(alignment_score("ab", "cb", 1, 1, 1, true), 0);
+    }
+}
@@ -0,0 +1,266 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::StateLimitExceeded;
+
+/// A deterministic automaton recognizing the strings within a fixed Levenshtein distance
+/// of a query, for testing many candidates against the same one
+///
+/// ```
+/// use agnostic_levenshtein::LevAutomaton;
+///
+/// let automaton = LevAutomaton::new("kitten", 2);
+/// assert_eq!(automaton.matches("mitten"), Some(1));
+/// assert_eq!(automaton.matches("sitting"), None);
+/// ```
+///
+/// Each state stands for a row of the DP matrix, with values above `max_dist` clamped
+/// (since they can no longer lead to a match). All states reachable from the first row
+/// are built up front, along with their transitions for each character of the query
+/// and for any other character. A candidate is then checked with one table lookup per
+/// character, stopping as soon as it can't match.
+///
+/// The number of states grows quickly with `max_dist` and the length of the query, so
+/// this suits small distances, as in spelling correction; construction fails past a
+/// limit on the number of states. Strings are compared as sequences of `char`s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LevAutomaton {
+    max_dist: u32,
+    // The distinct characters of the query, sorted
+    alphabet: Vec<char>,
+    // The next state from each state on each character of the alphabet, then on any
+    // other character. State 0 is the dead state, from which nothing matches; state 1 is
+    // the start.
+    transitions: Vec<usize>,
+    // The distance to the whole query from each state, if at most `max_dist`
+    distances: Vec<Option<u32>>,
+}
+
+impl LevAutomaton {
+    /// The limit on the number of states used by `new`
+    pub const DEFAULT_MAX_STATES: usize = 1 << 16;
+
+    /// Builds the automaton matching strings within `max_dist` of `query`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the automaton would need more than `DEFAULT_MAX_STATES` states; use
+    /// `try_new` to set another limit, or to handle the failure.
+    #[must_use]
+    pub fn new(query: &str, max_dist: u32) -> Self {
+        match Self::try_new(query, max_dist, Self::DEFAULT_MAX_STATES) {
+            Ok(automaton) => automaton,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Builds the automaton matching strings within `max_dist` of `query`, with at most
+    /// `max_states` states (counting the dead state)
+    ///
+    /// ```
+    /// use agnostic_levenshtein::LevAutomaton;
+    ///
+    /// assert!(LevAutomaton::try_new("kitten", 1, 100).is_ok());
+    /// assert!(LevAutomaton::try_new("kitten", u32::MAX, 100).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `StateLimitExceeded` as soon as more than `max_states` states have been
+    /// discovered, so the work done is bounded by the limit.
+    pub fn try_new(
+        query: &str,
+        max_dist: u32,
+        max_states: usize,
+    ) -> Result<Self, StateLimitExceeded> {
+        let query: Vec<char> = query.chars().collect();
+        let mut alphabet = query.clone();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+
+        let n = query.len();
+        let cap = max_dist.saturating_add(1);
+
+        let dead: Vec<u32> = alloc::vec![cap; n + 1];
+        let start: Vec<u32> = (0..=n as u32).map(|j| j.min(cap)).collect();
+
+        let mut states: Vec<Vec<u32>> = Vec::from([dead.clone(), start.clone()]);
+        let mut ids: BTreeMap<Vec<u32>, usize> = BTreeMap::from([(dead, 0), (start, 1)]);
+        let symbols = alphabet.len() + 1;
+        let mut transitions: Vec<usize> = alloc::vec![0; symbols];
+
+        // Each state's transitions are added in order, as new states are discovered
+        let mut current = 1;
+        while current < states.len() {
+            for symbol in 0..symbols {
+                let next = step(&states[current], &query, alphabet.get(symbol).copied(), cap);
+                let id = *ids.entry(next).or_insert_with_key(|row| {
+                    states.push(row.clone());
+                    states.len() - 1
+                });
+                if states.len() > max_states {
+                    return Err(StateLimitExceeded { max_states });
+                }
+                transitions.push(id);
+            }
+            current += 1;
+        }
+
+        let distances = states
+            .iter()
+            .map(|row| Some(row[n]).filter(|&dist| dist <= max_dist))
+            .collect();
+
+        Ok(Self {
+            max_dist,
+            alphabet,
+            transitions,
+            distances,
+        })
+    }
+
+    /// Returns the Levenshtein distance between the query and `candidate` if it is at
+    /// most the automaton's `max_dist`; otherwise `None`
+    #[must_use]
+    pub fn matches(&self, candidate: &str) -> Option<u32> {
+        let symbols = self.alphabet.len() + 1;
+        let mut state = 1;
+
+        for c in candidate.chars() {
+            let symbol = self
+                .alphabet
+                .binary_search(&c)
+                .unwrap_or(self.alphabet.len());
+            state = self.transitions[state * symbols + symbol];
+
+            if state == 0 {
+                return None;
+            }
+        }
+
+        self.distances[state]
+    }
+
+    /// Returns the maximum distance the automaton matches
+    #[must_use]
+    pub const fn max_dist(&self) -> u32 {
+        self.max_dist
+    }
+
+    /// Returns the number of states, including the dead state
+    #[must_use]
+    pub const fn state_count(&self) -> usize {
+        self.distances.len()
+    }
+}
+
+/// The DP row after `row` for the character `c` (`None` for one not in the query), with
+/// values clamped to `cap`
+fn step(row: &[u32], query: &[char], c: Option<char>, cap: u32) -> Vec<u32> {
+    let mut next: Vec<u32> = Vec::with_capacity(row.len());
+    next.push(row[0].saturating_add(1).min(cap));
+
+    for j in 1..row.len() {
+        let cost = u32::from(Some(query[j - 1]) != c);
+        let substitute = row[j - 1].saturating_add(cost);
+        let delete = row[j].saturating_add(1);
+        let insert = next[j - 1].saturating_add(1);
+        next.push(substitute.min(delete).min(insert).min(cap));
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance_within;
+
+    const WORDS: [&str; 12] = [
+        "kitten",
+        "sitting",
+        "mitten",
+        "kitchen",
+        "",
+        "k",
+        "nettik",
+        "kittens",
+        "maḥmūd",
+        "muḥammad",
+        "mahmud",
+        "ʿAlī ibn Abī Ṭālib",
+    ];
+
+    #[test]
+    fn agrees_with_dp() {
+        for query in WORDS {
+            for max_dist in 0..4 {
+                let automaton = LevAutomaton::new(query, max_dist);
+                for candidate in WORDS {
+                    assert_eq!(
+                        automaton.matches(candidate),
+                        edit_distance_within(query, candidate, false, max_dist),
+                        "{query:?} vs. {candidate:?} within {max_dist}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_candidates() {
+        // Every string over a small alphabet, up to length 5
+        let alphabet = ['a', 'b', 'ā'];
+        let mut candidates: Vec<String> = Vec::from([String::new()]);
+        let mut frontier = candidates.clone();
+        for _ in 0..5 {
+            frontier = frontier
+                .iter()
+                .flat_map(|s| alphabet.iter().map(move |&c| format!("{s}{c}")))
+                .collect();
+            candidates.extend(frontier.iter().cloned());
+        }
+
+        for query in ["abāa", "bb", ""] {
+            let automaton = LevAutomaton::new(query, 2);
+            for candidate in &candidates {
+                assert_eq!(
+                    automaton.matches(candidate),
+                    edit_distance_within(query, candidate, false, 2)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn states() {
+        let automaton = LevAutomaton::new("kitten", 1);
+        assert_eq!(automaton.max_dist(), 1);
+        assert!(automaton.state_count() > 2);
+        // Rejected as soon as it can't match
+        assert_eq!(automaton.matches("xyz and then some"), None);
+        assert_eq!(LevAutomaton::new("", 0).matches(""), Some(0));
+    }
+
+    #[test]
+    fn state_limit() {
+        let states = LevAutomaton::new("kitten", 1).state_count();
+        assert!(LevAutomaton::try_new("kitten", 1, states).is_ok());
+        assert_eq!(
+            LevAutomaton::try_new("kitten", 1, states - 1),
+            Err(StateLimitExceeded {
+                max_states: states - 1
+            })
+        );
+        // Otherwise unbounded, since rows would never be clamped
+        assert!(LevAutomaton::try_new("kitten", u32::MAX, 1000).is_err());
+        // Even the dead and start states must fit
+        assert!(LevAutomaton::try_new("", 0, 1).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 65536 states")]
+    fn new_past_default_limit() {
+        let _ = LevAutomaton::new("kitten", u32::MAX);
+    }
+}
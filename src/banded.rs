@@ -0,0 +1,121 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+use crate::{edge_case, shorter_first};
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, if it is at most
+/// `band`; otherwise `None`. The `ascii` flag works as for `edit_distance`.
+///
+/// Only the cells of the DP matrix within `band` of the main diagonal are computed (as
+/// in Ukkonen's cutoff), so the running time is proportional to the product of `band`
+/// and the string length rather than the product of the lengths. The result is exact
+/// whenever the true distance is at most `band`.
+#[must_use]
+pub fn banded_distance(a: &str, b: &str, ascii: bool, band: u32) -> Option<u32> {
+    if let Some(dist) = edge_case(a, b, ascii) {
+        return (dist <= band).then_some(dist);
+    }
+
+    if ascii {
+        banded_min_distance(a.as_bytes(), b.as_bytes(), band)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        banded_min_distance(&a_chars, &b_chars, band)
+    }
+}
+
+fn banded_min_distance<T: PartialEq>(a: &[T], b: &[T], band: u32) -> Option<u32> {
+    let (a, b) = shorter_first(a, b);
+    let m = a.len();
+    let n = b.len();
+
+    // Any path leaving the band costs more than `band`
+    if m.abs_diff(n) > band as usize {
+        return None;
+    }
+
+    // A band wider than the longer string is no band at all
+    let width = (band as usize).min(m.max(n));
+    // Stands in for the cells outside the band, which are all known to exceed it
+    let outside = width as u32 + 1;
+
+    let mut dp_prev: Vec<u32> = (0..=m)
+        .map(|j| if j <= width { j as u32 } else { outside })
+        .collect();
+    let mut dp_curr: Vec<u32> = vec![outside; m + 1];
+
+    for (i, b_char) in b.iter().enumerate() {
+        let row = i + 1;
+        let lo = row.saturating_sub(width).max(1);
+        let hi = (row + width).min(m);
+
+        dp_curr[lo - 1] = if lo == 1 { row as u32 } else { outside };
+        let mut row_min = dp_curr[lo - 1];
+
+        for j in lo..=hi {
+            if a[j - 1] == *b_char {
+                dp_curr[j] = dp_prev[j - 1];
+            } else {
+                let insert = dp_curr[j - 1] + 1;
+                let delete = dp_prev[j] + 1;
+                let substitute = dp_prev[j - 1] + 1;
+
+                dp_curr[j] = insert.min(delete).min(substitute);
+            }
+
+            row_min = row_min.min(dp_curr[j]);
+        }
+
+        // The next row reaches one column further, where this one left no value
+        if hi < m {
+            dp_curr[hi + 1] = outside;
+        }
+
+        if row_min > band {
+            return None;
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    (dp_prev[m] <= band).then_some(dp_prev[m])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn sitting_kitten() {
+        assert_eq!(banded_distance("sitting", "kitten", true, 3), Some(3));
+        assert_eq!(banded_distance("sitting", "kitten", true, 2), None);
+        assert_eq!(banded_distance("sitting", "kitten", false, 100), Some(3));
+    }
+
+    #[test]
+    fn exact_within_band() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        let dist = edit_distance(a, b, false);
+        assert_eq!(banded_distance(a, b, false, dist), Some(dist));
+        assert_eq!(banded_distance(a, b, false, dist + 1), Some(dist));
+        assert_eq!(banded_distance(a, b, false, dist - 1), None);
+    }
+
+    #[test]
+    fn length_difference() {
+        assert_eq!(banded_distance("abc", "abcdef", true, 2), None);
+        assert_eq!(banded_distance("abc", "abcdef", true, 3), Some(3));
+        assert_eq!(banded_distance("", "abc", true, 3), Some(3));
+    }
+
+    #[test]
+    fn off_diagonal() {
+        // The optimal path runs along the edge of the band
+        assert_eq!(banded_distance("xxabcdef", "abcdefyy", true, 4), Some(4));
+        assert_eq!(banded_distance("xxabcdef", "abcdefyy", true, 3), None);
+    }
+}
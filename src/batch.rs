@@ -0,0 +1,627 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::myers::MyersPattern;
+use crate::prep::{NormalizeOpts, normalize};
+use crate::{fits_u16, min_distance_buffered, min_distance_within_buffered};
+
+/// Returns the Levenshtein distances between `query` and each of `candidates`, in the
+/// same order. The `ascii` flag works as for `edit_distance`.
+///
+/// This is equivalent to calling `edit_distance` for each candidate, but the query is
+/// prepared only once and working buffers are reused across the whole batch.
+#[must_use]
+pub fn edit_distance_batch(query: &str, candidates: &[&str], ascii: bool) -> Vec<u32> {
+    let mut batch = Batch::new(query, ascii);
+    candidates.iter().map(|c| batch.distance(c)).collect()
+}
+
+/// Returns an iterator over the Levenshtein distances between `query` and each of
+/// `candidates`, computed lazily. The `ascii` flag works as for `edit_distance`.
+///
+/// As in `edit_distance_batch`, the query is prepared only once, but the candidates
+/// need not be collected first: they can come from any iterator, e.g., the lines of a
+/// file.
+pub fn distances<'a, I: Iterator<Item = &'a str> + 'a>(
+    query: &'a str,
+    candidates: I,
+    ascii: bool,
+) -> impl Iterator<Item = u32> + 'a {
+    let mut batch = Batch::new(query, ascii);
+    candidates.map(move |c| batch.distance(c))
+}
+
+/// Returns the candidate closest to `query`, along with its Levenshtein distance, or
+/// `None` if there are no candidates. The `ascii` flag works as for `edit_distance`.
+///
+/// Ties are broken in favor of the earliest candidate. Since only a strictly closer
+/// candidate can replace the best match so far, each comparison stops as soon as it is
+/// known not to be.
+#[must_use]
+pub fn closest_match<'a>(
+    query: &str,
+    candidates: &'a [&'a str],
+    ascii: bool,
+) -> Option<(&'a str, u32)> {
+    closest(&mut Batch::new(query, ascii), candidates, None)
+}
+
+/// Returns the closest candidate to `query`, ignoring differences of case, along with
+/// its distance, or `None` if there are no candidates. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// Case is handled as in `edit_distance_case_insensitive`, and ties as in
+/// `closest_match`. The query is lowercased once, and each candidate only if it has
+/// any uppercase letters.
+#[must_use]
+pub fn closest_match_case_insensitive<'a>(
+    query: &str,
+    candidates: &'a [&'a str],
+    ascii: bool,
+) -> Option<(&'a str, u32)> {
+    let opts = lowercase_opts(ascii);
+    let query = normalize(query, opts);
+    closest(&mut Batch::new(&query, ascii), candidates, Some(opts))
+}
+
+/// Returns the Levenshtein distances between `query` and each of `candidates`, ignoring
+/// differences of case, in the same order. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// This is equivalent to calling `edit_distance_case_insensitive` for each candidate,
+/// but the query is lowercased and prepared only once.
+#[must_use]
+pub fn edit_distance_batch_case_insensitive(
+    query: &str,
+    candidates: &[&str],
+    ascii: bool,
+) -> Vec<u32> {
+    let opts = lowercase_opts(ascii);
+    let query = normalize(query, opts);
+    let mut batch = Batch::new(&query, ascii);
+    candidates
+        .iter()
+        .map(|c| batch.distance(&normalize(c, opts)))
+        .collect()
+}
+
+/// The options with which `edit_distance_case_insensitive` prepares its strings
+fn lowercase_opts(ascii: bool) -> NormalizeOpts {
+    NormalizeOpts {
+        ascii,
+        lowercase: true,
+        ..NormalizeOpts::default()
+    }
+}
+
+/// The body of `closest_match`, normalizing each candidate first if `opts` are given
+fn closest<'a>(
+    batch: &mut Batch<'_>,
+    candidates: &'a [&'a str],
+    opts: Option<NormalizeOpts>,
+) -> Option<(&'a str, u32)> {
+    let mut best: Option<(&str, u32)> = None;
+
+    for &candidate in candidates {
+        let text = opts.map_or(Cow::Borrowed(candidate), |opts| normalize(candidate, opts));
+        let text: &str = &text;
+
+        let dist = match best {
+            None => Some(batch.distance(text)),
+            // Can't do better than an exact match
+            Some((_, 0)) => break,
+            Some((_, best_dist)) => batch.distance_within(text, best_dist - 1),
+        };
+
+        if let Some(dist) = dist {
+            best = Some((candidate, dist));
+        }
+    }
+
+    best
+}
+
+/// Returns the option closest to `input`, if it is within `max_dist`, for "did you mean"
+/// suggestions in error messages
+///
+/// ```
+/// use agnostic_levenshtein::suggest;
+///
+/// let commands = ["install", "uninstall", "update"];
+/// assert_eq!(suggest("instal", &commands, 2), Some("install"));
+/// assert_eq!(suggest("frobnicate", &commands, 2), None);
+/// ```
+///
+/// The comparison is case-insensitive, and in ASCII mode: ASCII letters are lowercased,
+/// and any other text is compared byte by byte. Ties are broken in favor of the earliest
+/// option, as in `closest_match`.
+#[must_use]
+pub fn suggest<'a>(input: &str, options: &'a [&'a str], max_dist: u32) -> Option<&'a str> {
+    let opts = NormalizeOpts {
+        ascii: true,
+        lowercase: true,
+        ..NormalizeOpts::default()
+    };
+
+    let input = normalize(input, opts);
+    let mut batch = Batch::new(&input, true);
+    let mut best: Option<(&str, u32)> = None;
+
+    for &option in options {
+        // Only a strictly closer option can replace the best so far
+        let Some(max) = best.map_or(Some(max_dist), |(_, dist)| dist.checked_sub(1)) else {
+            break;
+        };
+
+        if let Some(dist) = batch.distance_within(&normalize(option, opts), max) {
+            best = Some((option, dist));
+        }
+    }
+
+    best.map(|(option, _)| option)
+}
+
+/// Returns the candidates within `max` of `query`, along with their Levenshtein
+/// distances, in their original order. The `ascii` flag works as for `edit_distance`.
+///
+/// Each comparison stops as soon as the distance is known to exceed `max`, so far-off
+/// candidates are rejected cheaply.
+#[must_use]
+pub fn within_distance<'a>(
+    query: &str,
+    candidates: &'a [&'a str],
+    max: u32,
+    ascii: bool,
+) -> Vec<(&'a str, u32)> {
+    let mut batch = Batch::new(query, ascii);
+    candidates
+        .iter()
+        .filter_map(|&c| batch.distance_within(c, max).map(|dist| (c, dist)))
+        .collect()
+}
+
+/// Returns up to `k` of the candidates closest to `query`, along with their Levenshtein
+/// distances, sorted by distance. The `ascii` flag works as for `edit_distance`.
+///
+/// Ties are broken in favor of earlier candidates, both in the selection and in the
+/// ordering. Once `k` candidates have been found, each further comparison stops as soon
+/// as it is known not to improve on the worst of them.
+#[must_use]
+pub fn k_nearest<'a>(
+    query: &str,
+    candidates: &'a [&'a str],
+    k: usize,
+    ascii: bool,
+) -> Vec<(&'a str, u32)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut batch = Batch::new(query, ascii);
+    // Max-heap of (distance, index), so the worst match so far is on top
+    let mut heap: BinaryHeap<(u32, usize)> = BinaryHeap::with_capacity(k + 1);
+
+    for (idx, &candidate) in candidates.iter().enumerate() {
+        let dist = match heap.peek() {
+            Some(&(0, _)) if heap.len() == k => break,
+            Some(&(worst, _)) if heap.len() == k => batch.distance_within(candidate, worst - 1),
+            _ => Some(batch.distance(candidate)),
+        };
+
+        if let Some(dist) = dist {
+            heap.push((dist, idx));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(dist, idx)| (candidates[idx], dist))
+        .collect()
+}
+
+/// Returns the matrix of Levenshtein distances between all pairs of `items`, where
+/// `[i][j]` is the distance between `items[i]` and `items[j]`. The `ascii` flag works as
+/// for `edit_distance`.
+///
+/// Since distance is symmetric, only the pairs above the diagonal are computed, each
+/// item in turn being prepared as the query for those after it; the diagonal is zero.
+#[must_use]
+pub fn pairwise_distances(items: &[&str], ascii: bool) -> Vec<Vec<u32>> {
+    let n = items.len();
+    let mut matrix: Vec<Vec<u32>> = vec![vec![0; n]; n];
+
+    for (i, &item) in items.iter().enumerate() {
+        let mut batch = Batch::new(item, ascii);
+        for j in i + 1..n {
+            let dist = batch.distance(items[j]);
+            matrix[i][j] = dist;
+            matrix[j][i] = dist;
+        }
+    }
+
+    matrix
+}
+
+/// Parallel version of `edit_distance_batch`, spreading the work across all available
+/// cores with `rayon`. The results are identical, and in the same order. Requires the
+/// `rayon` feature.
+///
+/// Each of rayon's work items prepares its own copy of the query and working buffers,
+/// which are then reused for all the candidates it takes on. For small batches, the
+/// overhead outweighs any gain; prefer `edit_distance_batch`.
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn edit_distance_batch_parallel(query: &str, candidates: &[&str], ascii: bool) -> Vec<u32> {
+    candidates
+        .par_iter()
+        .map_init(|| Batch::new(query, ascii), |batch, c| batch.distance(c))
+        .collect()
+}
+
+/// Returns the Levenshtein distances between `query` and each line read from `reader`,
+/// in the same order. The `ascii` flag works as for `edit_distance`.
+///
+/// Lines may end in `\n` or `\r\n`, and surrounding whitespace is trimmed from each
+/// before comparison. Requires the `std` feature.
+///
+/// # Errors
+///
+/// Returns any error from reading, including invalid UTF-8.
+#[cfg(feature = "std")]
+pub fn distances_from_reader<R: std::io::BufRead>(
+    query: &str,
+    mut reader: R,
+    ascii: bool,
+) -> std::io::Result<Vec<u32>> {
+    let mut batch = Batch::new(query, ascii);
+    let mut dists: Vec<u32> = Vec::new();
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        dists.push(batch.distance(line.trim()));
+        line.clear();
+    }
+
+    Ok(dists)
+}
+
+/// A query prepared for comparison against any number of candidates
+struct Batch<'q> {
+    query: &'q str,
+    ascii: bool,
+    shape: Shape,
+    dp_prev: Vec<u32>,
+    dp_curr: Vec<u32>,
+    // Narrower rows, for when no distance can exceed `u16::MAX`
+    compact_prev: Vec<u16>,
+    compact_curr: Vec<u16>,
+    candidate_chars: Vec<char>,
+}
+
+/// How the query is represented, depending on the mode and its length
+enum Shape {
+    Myers(Box<MyersPattern>),
+    Bytes,
+    Chars(Vec<char>),
+}
+
+impl<'q> Batch<'q> {
+    fn new(query: &'q str, ascii: bool) -> Self {
+        let shape = if !ascii {
+            Shape::Chars(query.chars().collect())
+        } else if query.len() <= 64 {
+            Shape::Myers(Box::new(MyersPattern::new(query.as_bytes())))
+        } else {
+            Shape::Bytes
+        };
+
+        Self {
+            query,
+            ascii,
+            shape,
+            dp_prev: Vec::new(),
+            dp_curr: Vec::new(),
+            compact_prev: Vec::new(),
+            compact_curr: Vec::new(),
+            candidate_chars: Vec::new(),
+        }
+    }
+
+    fn distance(&mut self, candidate: &str) -> u32 {
+        if let Some(dist) = crate::edge_case(self.query, candidate, self.ascii) {
+            return dist;
+        }
+
+        match &self.shape {
+            Shape::Myers(pattern) => pattern.distance(candidate.as_bytes()),
+            Shape::Bytes => Self::dp(
+                self.query.as_bytes(),
+                candidate.as_bytes(),
+                (&mut self.dp_prev, &mut self.dp_curr),
+                (&mut self.compact_prev, &mut self.compact_curr),
+            ),
+            Shape::Chars(query_chars) => {
+                self.candidate_chars.clear();
+                self.candidate_chars.extend(candidate.chars());
+                Self::dp(
+                    query_chars,
+                    &self.candidate_chars,
+                    (&mut self.dp_prev, &mut self.dp_curr),
+                    (&mut self.compact_prev, &mut self.compact_curr),
+                )
+            }
+        }
+    }
+
+    /// The full DP, with whichever pair of buffers is narrowest for the lengths
+    fn dp<T: PartialEq>(
+        query: &[T],
+        candidate: &[T],
+        (dp_prev, dp_curr): (&mut Vec<u32>, &mut Vec<u32>),
+        (compact_prev, compact_curr): (&mut Vec<u16>, &mut Vec<u16>),
+    ) -> u32 {
+        if fits_u16(query.len(), candidate.len()) {
+            min_distance_buffered(query, candidate, compact_prev, compact_curr)
+        } else {
+            min_distance_buffered(query, candidate, dp_prev, dp_curr)
+        }
+    }
+
+    /// As `distance`, but returns `None` as soon as the distance is known to exceed `max`
+    fn distance_within(&mut self, candidate: &str, max: u32) -> Option<u32> {
+        if let Some(dist) = crate::edge_case(self.query, candidate, self.ascii) {
+            return (dist <= max).then_some(dist);
+        }
+
+        match &self.shape {
+            // Already fast enough that there's little to gain from stopping early
+            Shape::Myers(pattern) => {
+                let dist = pattern.distance(candidate.as_bytes());
+                (dist <= max).then_some(dist)
+            }
+            Shape::Bytes => min_distance_within_buffered(
+                self.query.as_bytes(),
+                candidate.as_bytes(),
+                max,
+                &mut self.dp_prev,
+                &mut self.dp_curr,
+            ),
+            Shape::Chars(query_chars) => {
+                self.candidate_chars.clear();
+                self.candidate_chars.extend(candidate.chars());
+                min_distance_within_buffered(
+                    query_chars,
+                    &self.candidate_chars,
+                    max,
+                    &mut self.dp_prev,
+                    &mut self.dp_curr,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    const WORDS: [&str; 8] = [
+        "kitten",
+        "sitting",
+        "",
+        "mitten",
+        "levenshtein",
+        "ʿAlī ibn Abī Ṭālib",
+        "شهنامه",
+        "the quick brown fox jumps over the lazy dog, again and again and again",
+    ];
+
+    #[test]
+    fn batch_matches_individual() {
+        for query in WORDS {
+            for ascii in [true, false] {
+                let expected: Vec<u32> = WORDS
+                    .iter()
+                    .map(|c| edit_distance(query, c, ascii))
+                    .collect();
+                assert_eq!(edit_distance_batch(query, &WORDS, ascii), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn closest() {
+        let words = ["sitting", "mitten", "kitchen", "bitten"];
+        assert_eq!(closest_match("kitten", &words, true), Some(("mitten", 1)));
+        assert_eq!(
+            closest_match("kitchen", &words, false),
+            Some(("kitchen", 0))
+        );
+        assert_eq!(closest_match("sittin", &words, true), Some(("sitting", 1)));
+        assert_eq!(closest_match("kitten", &[], true), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let words = ["Hallo", "hELLo", "yellow", "HELP"];
+        assert_eq!(
+            closest_match_case_insensitive("HELLO", &words, true),
+            Some(("hELLo", 0))
+        );
+        assert_eq!(
+            edit_distance_batch_case_insensitive("HELLO", &words, true),
+            [1, 0, 2, 2]
+        );
+
+        let words = ["ʿALĪ", "ʿumar", "ʿAlī ibn Abī Ṭālib"];
+        assert_eq!(
+            closest_match_case_insensitive("ʿalī", &words, false),
+            Some(("ʿALĪ", 0))
+        );
+        for query in WORDS.iter().chain(&words) {
+            for ascii in [true, false] {
+                let expected: Vec<u32> = WORDS
+                    .iter()
+                    .map(|c| crate::edit_distance_case_insensitive(query, c, ascii))
+                    .collect();
+                assert_eq!(
+                    edit_distance_batch_case_insensitive(query, &WORDS, ascii),
+                    expected
+                );
+            }
+        }
+        assert_eq!(closest_match_case_insensitive("HELLO", &[], true), None);
+    }
+
+    #[test]
+    fn closest_matches_batch() {
+        for query in WORDS {
+            for ascii in [true, false] {
+                let dists = edit_distance_batch(query, &WORDS, ascii);
+                let min = *dists.iter().min().unwrap();
+                let first = dists.iter().position(|&d| d == min).unwrap();
+                assert_eq!(
+                    closest_match(query, &WORDS, ascii),
+                    Some((WORDS[first], min))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nearest() {
+        let words = [
+            "sitting", "mitten", "kitchen", "bitten", "smitten", "kitten",
+        ];
+        assert_eq!(
+            k_nearest("kitten", &words, 3, true),
+            [("kitten", 0), ("mitten", 1), ("bitten", 1)]
+        );
+        assert_eq!(
+            k_nearest("kitten", &words, 2, false),
+            [("kitten", 0), ("mitten", 1)]
+        );
+        assert!(k_nearest("kitten", &words, 0, true).is_empty());
+    }
+
+    #[test]
+    fn nearest_k_too_large() {
+        let nearest = k_nearest("kitten", &WORDS, 100, false);
+        assert_eq!(nearest.len(), WORDS.len());
+
+        let mut expected: Vec<(&str, u32)> = WORDS
+            .iter()
+            .map(|&c| (c, edit_distance("kitten", c, false)))
+            .collect();
+        expected.sort_by_key(|&(_, dist)| dist);
+        assert_eq!(nearest, expected);
+    }
+
+    #[test]
+    fn within_one() {
+        let words = ["sitting", "mitten", "kitchen", "bitten", "kitten", "kit"];
+        assert_eq!(
+            within_distance("kitten", &words, 1, true),
+            [("mitten", 1), ("bitten", 1), ("kitten", 0)]
+        );
+        assert!(within_distance("kitten", &[], 1, false).is_empty());
+    }
+
+    #[test]
+    fn within_matches_batch() {
+        for query in WORDS {
+            for ascii in [true, false] {
+                let expected: Vec<(&str, u32)> = WORDS
+                    .iter()
+                    .zip(edit_distance_batch(query, &WORDS, ascii))
+                    .filter(|&(_, dist)| dist <= 3)
+                    .map(|(&c, dist)| (c, dist))
+                    .collect();
+                assert_eq!(within_distance(query, &WORDS, 3, ascii), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_from_lines() {
+        let text = WORDS.join("\n");
+        for ascii in [true, false] {
+            let lazy: Vec<u32> = distances("kitten", text.lines(), ascii).collect();
+            let expected = edit_distance_batch("kitten", &text.lines().collect::<Vec<_>>(), ascii);
+            assert_eq!(lazy, expected);
+        }
+
+        let close: Vec<u32> = distances("kitten", text.lines(), true)
+            .filter(|&d| d <= 1)
+            .take(2)
+            .collect();
+        assert_eq!(close, [0, 1]);
+    }
+
+    #[test]
+    fn suggestions() {
+        let commands = ["build", "install", "uninstall", "list", "search"];
+        assert_eq!(suggest("instal", &commands, 2), Some("install"));
+        assert_eq!(suggest("INSTAL", &commands, 1), Some("install"));
+        assert_eq!(suggest("lsit", &commands, 2), Some("list"));
+        assert_eq!(suggest("xyzzy", &commands, 2), None);
+        assert_eq!(suggest("sear", &commands, 1), None);
+        assert_eq!(suggest("build", &[], 2), None);
+    }
+
+    #[test]
+    fn pairwise() {
+        let matrix = pairwise_distances(&["kitten", "sitting", "mitten"], true);
+        assert_eq!(matrix, [[0, 3, 1], [3, 0, 3], [1, 3, 0]]);
+
+        let matrix = pairwise_distances(&WORDS, false);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0);
+            for (j, &dist) in row.iter().enumerate() {
+                assert_eq!(dist, matrix[j][i]);
+                assert_eq!(dist, edit_distance(WORDS[i], WORDS[j], false));
+            }
+        }
+
+        assert!(pairwise_distances(&[], true).is_empty());
+    }
+
+    #[test]
+    fn batch_empty() {
+        assert!(edit_distance_batch("kitten", &[], true).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_matches_sequential() {
+        let candidates: Vec<&str> = WORDS.iter().cycle().take(1000).copied().collect();
+        for ascii in [true, false] {
+            assert_eq!(
+                edit_distance_batch_parallel("sitting", &candidates, ascii),
+                edit_distance_batch("sitting", &candidates, ascii)
+            );
+        }
+        assert!(edit_distance_batch_parallel("sitting", &[], true).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_reader() {
+        let file = "sitting\r\nmitten\n  kitchen \n\nشهنامه";
+        let dists = distances_from_reader("kitten", std::io::Cursor::new(file), false).unwrap();
+        assert_eq!(dists, [3, 1, 2, 6, 6]);
+
+        let invalid = std::io::Cursor::new(b"kitten\n\xFF\n");
+        assert!(distances_from_reader("kitten", invalid, true).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::edit_distance;
+
+/// A BK-tree of words, for finding all those within a given Levenshtein distance of a
+/// query without comparing against every one
+///
+/// ```
+/// use agnostic_levenshtein::BkTree;
+///
+/// let mut tree = BkTree::new(true);
+/// for word in ["kitten", "sitting", "mitten", "fitting"] {
+///     tree.insert(word.to_string());
+/// }
+/// assert_eq!(tree.query("bitten", 1), [("kitten", 1), ("mitten", 1)]);
+/// ```
+///
+/// Each child of a node is keyed by its distance from the node's word. By the triangle
+/// inequality, a query only needs to descend into children whose key is within
+/// `max_dist` of the query's own distance from the node. The default tree compares in
+/// Unicode mode, as does `edit_distance` with the `ascii` flag unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BkTree {
+    ascii: bool,
+    // The first node, if any, is the root
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node {
+    word: String,
+    // Distance from `word` to the child's word, and the child's index
+    children: BTreeMap<u32, usize>,
+}
+
+impl BkTree {
+    /// Creates an empty tree. The `ascii` flag works as for `edit_distance`.
+    #[must_use]
+    pub const fn new(ascii: bool) -> Self {
+        Self {
+            ascii,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Adds `word` to the tree, unless it's already present
+    pub fn insert(&mut self, word: String) {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node::new(word));
+            return;
+        }
+
+        let mut idx = 0;
+        loop {
+            let dist = edit_distance(&self.nodes[idx].word, &word, self.ascii);
+            if dist == 0 {
+                return;
+            }
+
+            if let Some(&child) = self.nodes[idx].children.get(&dist) {
+                idx = child;
+            } else {
+                let child = self.nodes.len();
+                self.nodes.push(Node::new(word));
+                self.nodes[idx].children.insert(dist, child);
+                return;
+            }
+        }
+    }
+
+    /// Returns all words in the tree within `max_dist` of `word`, along with their
+    /// distances, sorted by distance and then alphabetically
+    #[must_use]
+    pub fn query(&self, word: &str, max_dist: u32) -> Vec<(&str, u32)> {
+        let mut found: Vec<(&str, u32)> = Vec::new();
+        if self.nodes.is_empty() {
+            return found;
+        }
+
+        let mut stack = vec![0];
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let dist = edit_distance(&node.word, word, self.ascii);
+            if dist <= max_dist {
+                found.push((&node.word, dist));
+            }
+
+            let lo = dist.saturating_sub(max_dist);
+            let hi = dist.saturating_add(max_dist);
+            stack.extend(node.children.range(lo..=hi).map(|(_, &child)| child));
+        }
+
+        found.sort_unstable_by_key(|&(word, dist)| (dist, word));
+        found
+    }
+
+    /// Returns the number of words in the tree
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the tree contains no words
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl Node {
+    const fn new(word: String) -> Self {
+        Self {
+            word,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    const VOCAB: [&str; 10] = [
+        "kitten", "sitting", "mitten", "fitting", "bitten", "smitten", "kitchen", "written",
+        "sitter", "knitting",
+    ];
+
+    fn tree(ascii: bool) -> BkTree {
+        let mut tree = BkTree::new(ascii);
+        for word in VOCAB {
+            tree.insert(word.to_string());
+        }
+        tree
+    }
+
+    #[test]
+    fn query_within_two() {
+        for ascii in [true, false] {
+            let tree = tree(ascii);
+            let mut found = tree.query("kitten", 2);
+            found.sort_unstable();
+
+            let mut expected: Vec<(&str, u32)> = VOCAB
+                .iter()
+                .map(|&w| (w, edit_distance(w, "kitten", ascii)))
+                .filter(|&(_, dist)| dist <= 2)
+                .collect();
+            expected.sort_unstable();
+
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn sorted_by_distance() {
+        let tree = tree(true);
+        let found = tree.query("sittin", 2);
+        assert_eq!(found[0], ("sitting", 1));
+        assert!(
+            found
+                .windows(2)
+                .all(|w| (w[0].1, w[0].0) < (w[1].1, w[1].0))
+        );
+    }
+
+    #[test]
+    fn duplicates_and_empty() {
+        let mut tree = tree(false);
+        tree.insert("kitten".to_string());
+        assert_eq!(tree.len(), VOCAB.len());
+
+        let empty = BkTree::default();
+        assert!(empty.is_empty());
+        assert!(empty.query("kitten", 5).is_empty());
+    }
+}
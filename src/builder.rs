@@ -0,0 +1,256 @@
+use alloc::vec::Vec;
+
+use crate::prep::{NormalizeOpts, normalize};
+use crate::weighted::weighted_min_distance;
+use crate::{
+    EditError, Weights, edit_distance, edit_distance_within, min_distance, min_distance_within,
+};
+
+/// A configurable edit-distance computation, combining the options otherwise spread
+/// across separate functions
+///
+/// ```
+/// use agnostic_levenshtein::Levenshtein;
+///
+/// let lev = Levenshtein::default().ascii(true).case_insensitive(true);
+/// assert_eq!(lev.distance("Kitten", "SITTING"), Some(3));
+/// assert_eq!(lev.max_distance(Some(2)).distance("Kitten", "SITTING"), None);
+/// ```
+///
+/// The default configuration matches `edit_distance` with the `ascii` flag unset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Levenshtein {
+    ascii: bool,
+    case_insensitive: bool,
+    #[cfg(feature = "normalization")]
+    nfc: bool,
+    weights: Weights,
+    max_distance: Option<u32>,
+}
+
+impl Levenshtein {
+    /// Whether the strings can be treated as ASCII-only, as for `edit_distance`
+    #[must_use]
+    pub const fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Whether to ignore differences of case, as in `edit_distance_case_insensitive`
+    #[must_use]
+    pub const fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Whether to apply NFC normalization first, as in `edit_distance_normalized`.
+    /// Requires the `normalization` feature.
+    #[cfg(feature = "normalization")]
+    #[must_use]
+    pub const fn nfc(mut self, nfc: bool) -> Self {
+        self.nfc = nfc;
+        self
+    }
+
+    /// Costs of the individual edit operations, as in `weighted_distance`
+    #[must_use]
+    pub const fn weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// A distance beyond which to give up, as in `edit_distance_within`
+    #[must_use]
+    pub const fn max_distance(mut self, max_distance: Option<u32>) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Returns the distance between `a` and `b` under this configuration, or `None` if
+    /// it exceeds the maximum distance (if one is set)
+    #[must_use]
+    pub fn distance(&self, a: &str, b: &str) -> Option<u32> {
+        self.max_distance.map_or_else(
+            || Some(self.unbounded_distance(a, b)),
+            |max| self.bounded_distance(a, b, max),
+        )
+    }
+
+    /// The distance under this configuration, ignoring any maximum
+    fn unbounded_distance(&self, a: &str, b: &str) -> u32 {
+        // Without preprocessing or weights, the dedicated functions are fastest
+        if self.is_plain() {
+            return edit_distance(a, b, self.ascii);
+        }
+
+        let (a, b) = (normalize(a, self.opts()), normalize(b, self.opts()));
+        if self.ascii {
+            self.slice_distance(a.as_bytes(), b.as_bytes())
+        } else {
+            let a_chars: Vec<char> = a.chars().collect();
+            let b_chars: Vec<char> = b.chars().collect();
+            self.slice_distance(&a_chars, &b_chars)
+        }
+    }
+
+    /// The distance under this configuration if it is at most `max`
+    fn bounded_distance(&self, a: &str, b: &str, max: u32) -> Option<u32> {
+        if self.is_plain() {
+            return edit_distance_within(a, b, self.ascii, max);
+        }
+
+        let (a, b) = (normalize(a, self.opts()), normalize(b, self.opts()));
+        if self.ascii {
+            self.slice_distance_within(a.as_bytes(), b.as_bytes(), max)
+        } else {
+            let a_chars: Vec<char> = a.chars().collect();
+            let b_chars: Vec<char> = b.chars().collect();
+            self.slice_distance_within(&a_chars, &b_chars, max)
+        }
+    }
+
+    /// Whether the options neither transform the strings nor weight the edits
+    fn is_plain(&self) -> bool {
+        !self.preprocesses() && self.weights == Weights::default()
+    }
+
+    /// Whether any of the options transform the strings before comparing them
+    const fn preprocesses(&self) -> bool {
+        #[cfg(feature = "normalization")]
+        if self.nfc {
+            return true;
+        }
+
+        self.case_insensitive
+    }
+
+    fn opts(&self) -> NormalizeOpts {
+        NormalizeOpts {
+            ascii: self.ascii,
+            lowercase: self.case_insensitive,
+            #[cfg(feature = "normalization")]
+            nfc: self.nfc,
+            ..NormalizeOpts::default()
+        }
+    }
+
+    fn slice_distance<T: PartialEq>(&self, a: &[T], b: &[T]) -> u32 {
+        if self.weights == Weights::default() {
+            min_distance(a, b)
+        } else {
+            weighted_min_distance(a, b, &self.weights)
+        }
+    }
+
+    fn slice_distance_within<T: PartialEq>(&self, a: &[T], b: &[T], max: u32) -> Option<u32> {
+        if self.weights == Weights::default() {
+            min_distance_within(a, b, max)
+        } else {
+            Some(weighted_min_distance(a, b, &self.weights)).filter(|&dist| dist <= max)
+        }
+    }
+}
+
+/// Returns the distance between `a` and `b` under the configuration `cfg`, as with
+/// `Levenshtein::distance`, but failing rather than giving a misleading result.
+///
+/// Any maximum distance set in `cfg` is ignored, so the full distance is always
+/// returned; use `Levenshtein::distance` for a bounded comparison.
+///
+/// # Errors
+///
+/// Returns `EditError::IncompatibleOptions` if `cfg` combines ASCII mode with NFC
+/// normalization, which only affects non-ASCII characters (so this can only happen with
+/// the `normalization` feature); and `EditError::NonAsciiInput` if ASCII mode is set but
+/// either string is not ASCII-only.
+pub fn try_edit_distance(cfg: &Levenshtein, a: &str, b: &str) -> Result<u32, EditError> {
+    if cfg.ascii {
+        #[cfg(feature = "normalization")]
+        if cfg.nfc {
+            return Err(EditError::IncompatibleOptions);
+        }
+
+        if !(a.is_ascii() && b.is_ascii()) {
+            return Err(EditError::NonAsciiInput);
+        }
+    }
+
+    Ok(cfg.unbounded_distance(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{edit_distance_case_insensitive, weighted_distance};
+
+    #[test]
+    fn default_matches_edit_distance() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        let lev = Levenshtein::default();
+        assert_eq!(lev.distance(a, b), Some(edit_distance(a, b, false)));
+        assert_eq!(lev.distance("", b), Some(edit_distance("", b, false)));
+    }
+
+    #[test]
+    fn case_insensitive_with_max() {
+        let lev = Levenshtein::default().case_insensitive(true);
+        assert_eq!(lev.distance("McDonald", "mcdonald"), Some(0));
+        assert_eq!(
+            lev.distance("Straße", "STRASSE"),
+            Some(edit_distance_case_insensitive("Straße", "STRASSE", false))
+        );
+
+        let capped = lev.ascii(true).max_distance(Some(1));
+        assert_eq!(capped.distance("McDonald", "MacDonald"), Some(1));
+        assert_eq!(capped.distance("McDonald", "MacDonnell"), None);
+    }
+
+    #[test]
+    fn weighted() {
+        let weights = Weights {
+            insert: 1,
+            delete: 5,
+            substitute: 3,
+        };
+        let lev = Levenshtein::default().weights(weights);
+        assert_eq!(lev.distance("abc", "xbc"), Some(3));
+        assert_eq!(
+            lev.distance("sitting", "kitten"),
+            Some(weighted_distance("sitting", "kitten", false, &weights))
+        );
+        assert_eq!(lev.max_distance(Some(2)).distance("abc", "xbc"), None);
+
+        let lev = lev.case_insensitive(true);
+        assert_eq!(lev.distance("ABC", "xbc"), Some(3));
+    }
+
+    #[test]
+    fn try_ok_and_non_ascii() {
+        let lev = Levenshtein::default().ascii(true);
+        assert_eq!(try_edit_distance(&lev, "kitten", "sitting"), Ok(3));
+        assert_eq!(
+            try_edit_distance(&lev, "شاهنامه", "شهنامه"),
+            Err(EditError::NonAsciiInput)
+        );
+
+        let lev = lev.ascii(false);
+        assert_eq!(try_edit_distance(&lev, "شاهنامه", "شهنامه"), Ok(1));
+
+        // The maximum distance doesn't apply
+        let lev = lev.max_distance(Some(0));
+        assert_eq!(lev.distance("شاهنامه", "شهنامه"), None);
+        assert_eq!(try_edit_distance(&lev, "شاهنامه", "شهنامه"), Ok(1));
+    }
+
+    #[test]
+    #[cfg(feature = "normalization")]
+    fn try_incompatible() {
+        let lev = Levenshtein::default().nfc(true);
+        assert_eq!(try_edit_distance(&lev, "caf\u{E9}", "cafe\u{301}"), Ok(0));
+        assert_eq!(
+            try_edit_distance(&lev.ascii(true), "cafe", "cafe"),
+            Err(EditError::IncompatibleOptions)
+        );
+    }
+}
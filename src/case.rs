@@ -0,0 +1,115 @@
+#[cfg(feature = "casefold")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "casefold")]
+use caseless::Caseless;
+
+use crate::edit_distance;
+#[cfg(feature = "casefold")]
+use crate::edit_distance_slice;
+use crate::prep::{NormalizeOpts, normalize};
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, ignoring
+/// differences of case. The `ascii` flag works as for `edit_distance`.
+///
+/// In ASCII mode, bytes are lowercased with `u8::to_ascii_lowercase`; otherwise, each
+/// `char` is lowercased with `char::to_lowercase`. Note that the latter can expand one
+/// `char` into several (e.g., "İ" becomes "i̇"), which then count as separate units. This
+/// is simple lowercasing, not full case folding, so "ß" and "ss" are still distinct.
+#[must_use]
+pub fn edit_distance_case_insensitive(a: &str, b: &str, ascii: bool) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let opts = NormalizeOpts {
+        ascii,
+        lowercase: true,
+        ..NormalizeOpts::default()
+    };
+    edit_distance(&normalize(a, opts), &normalize(b, opts), ascii)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, after full
+/// Unicode case folding of both. Strings are compared as sequences of `char`s.
+///
+/// Case folding is meant for caseless matching, and it goes further than the simple
+/// lowercasing of `edit_distance_case_insensitive`: e.g., final "ς" folds to "σ", like
+/// "Σ", and "ß" folds to "ss". Some `char`s fold to several, which then count as
+/// separate units. Folding is done by the `caseless` crate; requires the `casefold`
+/// feature (which implies `std`).
+#[cfg(feature = "casefold")]
+#[must_use]
+pub fn edit_distance_casefold(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    edit_distance_slice(&fold_chars(a), &fold_chars(b))
+}
+
+/// The `char`s of `s`, case-folded (possibly expanding some of them)
+#[cfg(feature = "casefold")]
+fn fold_chars(s: &str) -> Vec<char> {
+    s.chars().default_case_fold().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_casing() {
+        assert_eq!(
+            edit_distance_case_insensitive("McDonald", "mcdonald", true),
+            0
+        );
+        assert_eq!(
+            edit_distance_case_insensitive("McDonald", "MacDONALD", true),
+            1
+        );
+    }
+
+    #[test]
+    fn unicode_casing() {
+        assert_eq!(edit_distance_case_insensitive("ʿALĪ", "ʿalī", false), 0);
+        // Non-ASCII bytes are unaffected in ASCII mode
+        assert_eq!(edit_distance_case_insensitive("ʿALĪ", "ʿalī", true), 1);
+    }
+
+    #[test]
+    fn strasse() {
+        assert_eq!(
+            edit_distance_case_insensitive("Straße", "STRASSE", false),
+            2
+        );
+        assert_eq!(
+            edit_distance_case_insensitive("Straße", "strasse", false),
+            2
+        );
+    }
+
+    #[test]
+    fn expansion() {
+        // "İ" lowercases to "i" plus a combining dot above
+        assert_eq!(edit_distance_case_insensitive("İ", "i", false), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "casefold")]
+    fn casefold_sigma() {
+        let a = "ΣΟΦΟΣ";
+        let b = "σοφος";
+        assert_eq!(edit_distance_casefold(a, b), 0);
+        // Simple lowercasing leaves the final sigma distinct
+        assert_eq!(edit_distance_case_insensitive(a, b, false), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "casefold")]
+    fn casefold_expanding() {
+        assert_eq!(edit_distance_casefold("Straße", "STRASSE"), 0);
+        assert_eq!(edit_distance_casefold("ʿALĪ", "ʿalī"), 0);
+        assert_eq!(edit_distance_casefold("ʿALĪ", "ʿUmar"), 4);
+    }
+}
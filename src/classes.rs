@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+
+use crate::edit_distance_slice;
+
+/// A class of characters that count as the same for `edit_distance_classes`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CharClass {
+    /// Numeric characters, as by `char::is_numeric`: digits in any script (e.g., "7" and
+    /// "٧"), but also, e.g., "½"
+    Digit,
+    /// Whitespace, as by `char::is_whitespace`
+    Whitespace,
+    /// ASCII punctuation, as by `char::is_ascii_punctuation`
+    Punctuation,
+    /// The given characters
+    Set(Vec<char>),
+}
+
+impl CharClass {
+    /// Whether `c` belongs to the class
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Self::Digit => c.is_numeric(),
+            Self::Whitespace => c.is_whitespace(),
+            Self::Punctuation => c.is_ascii_punctuation(),
+            Self::Set(chars) => chars.contains(&c),
+        }
+    }
+}
+
+/// A `char`, or the class standing in for it
+#[derive(PartialEq)]
+enum Unit {
+    Char(char),
+    Class(usize),
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, where any
+/// two members of the same one of `classes` count as equal
+///
+/// ```
+/// use agnostic_levenshtein::{CharClass, edit_distance_classes};
+///
+/// assert_eq!(edit_distance_classes("a1b", "a9b", &[CharClass::Digit]), 0);
+/// assert_eq!(edit_distance_classes("a1b", "a9b", &[]), 1);
+/// ```
+///
+/// Each character is replaced by the first of `classes` to which it belongs, if any, so
+/// a character in several classes is equal only to the members of the first. Strings are
+/// compared as sequences of `char`s.
+#[must_use]
+pub fn edit_distance_classes(a: &str, b: &str, classes: &[CharClass]) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let units = |s: &str| -> Vec<Unit> {
+        s.chars()
+            .map(|c| {
+                classes
+                    .iter()
+                    .position(|class| class.contains(c))
+                    .map_or(Unit::Char(c), Unit::Class)
+            })
+            .collect()
+    };
+
+    edit_distance_slice(&units(a), &units(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn equivalent_classes() {
+        let digits = [CharClass::Digit];
+        assert_eq!(edit_distance_classes("a1b", "a9b", &digits), 0);
+        assert_eq!(edit_distance_classes("page ١٢", "page 34", &digits), 0);
+        assert_eq!(edit_distance_classes("a1b", "aab", &digits), 1);
+
+        let lenient = [CharClass::Whitespace, CharClass::Punctuation];
+        assert_eq!(edit_distance_classes("ibn\tʿAlī", "ibn ʿAlī", &lenient), 0);
+        assert_eq!(edit_distance_classes("al-Ṭūsī", "al_Ṭūsī", &lenient), 0);
+        // Whitespace and punctuation are still distinct from each other
+        assert_eq!(edit_distance_classes("al-Ṭūsī", "al Ṭūsī", &lenient), 1);
+
+        let vowels = [CharClass::Set(Vec::from(['a', 'ā', 'u', 'ū']))];
+        assert_eq!(edit_distance_classes("maḥmūd", "muḥmad", &vowels), 0);
+    }
+
+    #[test]
+    fn first_class_wins() {
+        let classes = [CharClass::Digit, CharClass::Set(Vec::from(['1', 'x']))];
+        assert_eq!(edit_distance_classes("1", "x", &classes), 1);
+        assert_eq!(edit_distance_classes("1", "2", &classes), 0);
+        assert!(classes[1].contains('x'));
+    }
+
+    #[test]
+    fn no_classes() {
+        let (a, b) = ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān");
+        assert_eq!(edit_distance_classes(a, b, &[]), edit_distance(a, b, false));
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+/// Equality for the purposes of edit distance, as used by `edit_distance_by`
+///
+/// Every `PartialEq` type implements this via `==`. For domain-specific equality—e.g.,
+/// phonemes of which some are interchangeable—implement it on a type that is not
+/// `PartialEq` (or on a newtype wrapper).
+pub trait EditComparable {
+    /// Whether `self` and `other` count as the same element, so that no edit is needed
+    fn edit_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: PartialEq> EditComparable for T {
+    fn edit_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// Returns the Levenshtein distance (`u32`) between two slices, `a` and `b`, comparing
+/// elements with `EditComparable::edit_eq`.
+///
+/// For `PartialEq` types, this is the same as `edit_distance_slice`.
+#[must_use]
+pub fn edit_distance_by<T: EditComparable>(a: &[T], b: &[T]) -> u32 {
+    let m = a.len();
+
+    let mut dp_prev: Vec<u32> = (0..=m as u32).collect();
+    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+
+    for (i, b_elem) in b.iter().enumerate() {
+        dp_curr[0] = i as u32 + 1;
+
+        for j in 1..=m {
+            if a[j - 1].edit_eq(b_elem) {
+                dp_curr[j] = dp_prev[j - 1];
+                continue;
+            }
+
+            let insert = dp_curr[j - 1] + 1;
+            let delete = dp_prev[j] + 1;
+            let substitute = dp_prev[j - 1] + 1;
+
+            dp_curr[j] = insert.min(delete).min(substitute);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance_slice;
+
+    /// Vowel phonemes, of which the two long ones are treated as interchangeable
+    #[derive(Debug, Clone, Copy)]
+    enum Vowel {
+        A,
+        LongA,
+        LongO,
+        I,
+    }
+
+    impl EditComparable for Vowel {
+        fn edit_eq(&self, other: &Self) -> bool {
+            use Vowel::{A, I, LongA, LongO};
+            matches!(
+                (self, other),
+                (A, A) | (I, I) | (LongA | LongO, LongA | LongO)
+            )
+        }
+    }
+
+    #[test]
+    fn custom_equality() {
+        use Vowel::{A, I, LongA, LongO};
+        assert_eq!(edit_distance_by(&[A, LongA, I], &[A, LongO, I]), 0);
+        assert_eq!(edit_distance_by(&[A, LongA, I], &[LongO, A, I]), 2);
+        assert_eq!(edit_distance_by(&[A, I], &[]), 2);
+    }
+
+    #[test]
+    fn partial_eq_unchanged() {
+        let a: Vec<char> = "ʿAlī ibn Abī Ṭālib".chars().collect();
+        let b: Vec<char> = "ʿUthmān ibn ʿAffān".chars().collect();
+        assert_eq!(edit_distance_by(&a, &b), edit_distance_slice(&a, &b));
+        assert_eq!(edit_distance_by(b"kitten", b"sitting"), 3);
+    }
+}
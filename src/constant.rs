@@ -0,0 +1,82 @@
+/// Returns the Levenshtein distance (`u32`) between two byte arrays, `a` and `b`, in a
+/// form that can be evaluated at compile time.
+///
+/// This is the same computation as the ASCII mode of `edit_distance`, restricted to what
+/// a `const fn` can do: there is no allocation, only a single row of the DP matrix held
+/// in a fixed-size array. That makes it most useful for short literals, e.g., in static
+/// tables.
+///
+/// ```
+/// use agnostic_levenshtein::edit_distance_const;
+///
+/// const DIST: u32 = edit_distance_const(b"kitten", b"sitting");
+/// assert_eq!(DIST, 3);
+/// ```
+#[must_use]
+pub const fn edit_distance_const<const N: usize, const M: usize>(a: &[u8; N], b: &[u8; M]) -> u32 {
+    if N == 0 {
+        return M as u32;
+    }
+
+    // `row[j]` holds the distance to the prefix of `a` of length `j + 1`; the column for
+    // the empty prefix is tracked separately, since `[u32; N + 1]` can't be expressed
+    let mut row = [0; N];
+    let mut j = 0;
+    while j < N {
+        row[j] = j as u32 + 1;
+        j += 1;
+    }
+
+    let mut i = 0;
+    while i < M {
+        // The previous row's value in the column before `j`, and this row's
+        let mut diag = i as u32;
+        let mut left = i as u32 + 1;
+
+        let mut j = 0;
+        while j < N {
+            let above = row[j];
+
+            let curr = if a[j] == b[i] {
+                diag
+            } else {
+                min(min(left, above), diag) + 1
+            };
+
+            diag = above;
+            row[j] = curr;
+            left = curr;
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    row[N - 1]
+}
+
+const fn min(x: u32, y: u32) -> u32 {
+    if x < y { x } else { y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    const _: () = assert!(edit_distance_const(b"abc", b"abd") == 1);
+    const _: () = assert!(edit_distance_const(b"kitten", b"sitting") == 3);
+    const _: () = assert!(edit_distance_const(b"", b"abc") == 3);
+    const _: () = assert!(edit_distance_const(b"abc", b"") == 3);
+
+    #[test]
+    fn const_matches_runtime() {
+        let a = b"Firdawsi";
+        let b = b"Ferdowsi";
+        assert_eq!(
+            edit_distance_const(a, b),
+            edit_distance("Firdawsi", "Ferdowsi", true)
+        );
+        assert_eq!(edit_distance_const(a, a), 0);
+    }
+}
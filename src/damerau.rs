@@ -0,0 +1,203 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+use crate::edge_case;
+
+/// Returns the Damerau-Levenshtein distance (`u32`) between two strings (`&str`), `a`
+/// and `b`. The `ascii` flag indicates whether the strings can be treated as ASCII-only.
+///
+/// This is the Levenshtein distance with the addition of (unrestricted) transpositions
+/// of adjacent characters, each counted as a single edit. Compare `osa_distance`.
+#[must_use]
+pub fn damerau_distance(a: &str, b: &str, ascii: bool) -> u32 {
+    if let Some(dist) = edge_case(a, b, ascii) {
+        return dist;
+    }
+
+    if ascii {
+        damerau_min_distance(a.as_bytes(), b.as_bytes())
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        damerau_min_distance(&a_chars, &b_chars)
+    }
+}
+
+/// Returns the optimal string alignment distance (`u32`) between two strings, `a` and
+/// `b`. The `ascii` flag works as for `edit_distance`.
+///
+/// Like `damerau_distance`, this counts the transposition of two adjacent characters as
+/// a single edit, but no substring may be edited more than once. So a transposed pair
+/// can't then have a character inserted between them: "ca" and "abc" are at distance 3
+/// here (e.g., two substitutions and an insertion), but at distance 2 by
+/// `damerau_distance` ("ca" → "ac" → "abc"). This restricted form is cheaper to compute,
+/// and it's what is often meant—sometimes mistakenly—by "Damerau-Levenshtein".
+#[must_use]
+pub fn osa_distance(a: &str, b: &str, ascii: bool) -> u32 {
+    if let Some(dist) = edge_case(a, b, ascii) {
+        return dist;
+    }
+
+    if ascii {
+        osa_min_distance(a.as_bytes(), b.as_bytes())
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        osa_min_distance(&a_chars, &b_chars)
+    }
+}
+
+fn damerau_min_distance<T: Ord + Copy>(a: &[T], b: &[T]) -> u32 {
+    // We already know: strings are not equal; neither string is empty
+    let m = a.len();
+    let n = b.len();
+
+    // A transposition can reach back arbitrarily far, so we need the full matrix. It has
+    // an extra leading row and column holding a sentinel greater than any distance.
+    let max_dist = (m + n) as u32;
+    let width = m + 2;
+    let mut dp: Vec<u32> = vec![0; width * (n + 2)];
+
+    dp[0] = max_dist;
+    for j in 0..=m {
+        dp[width + j + 1] = j as u32;
+        dp[j + 1] = max_dist;
+    }
+    for i in 0..=n {
+        dp[(i + 1) * width + 1] = i as u32;
+        dp[(i + 1) * width] = max_dist;
+    }
+
+    // Last row (1-indexed) of `b` in which each element was seen
+    let mut last_row: BTreeMap<T, usize> = BTreeMap::new();
+
+    for (i, b_char) in b.iter().enumerate() {
+        let row = i + 2;
+        // Last column (1-indexed) of `a` in this row that matched `b_char`
+        let mut last_match_col = 0;
+
+        for (j, a_char) in a.iter().enumerate() {
+            let col = j + 2;
+            let prev_row = last_row.get(a_char).copied().unwrap_or(0);
+            let prev_col = last_match_col;
+
+            let cost = if a_char == b_char {
+                last_match_col = j + 1;
+                0
+            } else {
+                1
+            };
+
+            let insert = dp[row * width + col - 1] + 1;
+            let delete = dp[(row - 1) * width + col] + 1;
+            let substitute = dp[(row - 1) * width + col - 1] + cost;
+            // Everything between the transposed pair is deleted or inserted
+            let transpose =
+                dp[prev_row * width + prev_col] + (i - prev_row + 1 + j - prev_col) as u32;
+
+            dp[row * width + col] = insert.min(delete).min(substitute).min(transpose);
+        }
+
+        last_row.insert(*b_char, i + 1);
+    }
+
+    dp[(n + 1) * width + m + 1]
+}
+
+fn osa_min_distance<T: PartialEq>(a: &[T], b: &[T]) -> u32 {
+    let m = a.len();
+
+    // A transposition looks back two rows, so we keep three
+    let mut dp_prev2: Vec<u32> = vec![0; m + 1];
+    let mut dp_prev: Vec<u32> = (0..=m as u32).collect();
+    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+
+    for (i, b_char) in b.iter().enumerate() {
+        dp_curr[0] = i as u32 + 1;
+
+        for j in 1..=m {
+            let cost = u32::from(a[j - 1] != *b_char);
+
+            let insert = dp_curr[j - 1] + 1;
+            let delete = dp_prev[j] + 1;
+            let substitute = dp_prev[j - 1] + cost;
+            let mut dist = insert.min(delete).min(substitute);
+
+            if i > 0 && j > 1 && a[j - 1] == b[i - 1] && a[j - 2] == *b_char {
+                dist = dist.min(dp_prev2[j - 2] + 1);
+            }
+
+            dp_curr[j] = dist;
+        }
+
+        // Rotate the rows: `prev` becomes `prev2`, `curr` becomes `prev`
+        swap(&mut dp_prev2, &mut dp_prev);
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn teh_the() {
+        assert_eq!(damerau_distance("teh", "the", true), 1);
+        assert_eq!(edit_distance("teh", "the", true), 2);
+    }
+
+    #[test]
+    fn ca_abc() {
+        assert_eq!(damerau_distance("ca", "abc", true), 2);
+        assert_eq!(damerau_distance("ca", "abc", false), 2);
+    }
+
+    #[test]
+    fn ca_abc_osa() {
+        assert_eq!(osa_distance("ca", "abc", true), 3);
+        assert_eq!(osa_distance("ca", "abc", false), 3);
+        assert_eq!(osa_distance("teh", "the", true), 1);
+    }
+
+    #[test]
+    fn osa_between_damerau_and_levenshtein() {
+        let pairs = [
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("شاهنامه", "شهانامه"),
+            ("abcdef", "badcfe"),
+            ("Firdawsi", "Fridawis"),
+        ];
+
+        for (a, b) in pairs {
+            let osa = osa_distance(a, b, false);
+            assert!(damerau_distance(a, b, false) <= osa);
+            assert!(osa <= edit_distance(a, b, false));
+        }
+    }
+
+    #[test]
+    fn no_transpositions() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        assert_eq!(damerau_distance(a, b, false), edit_distance(a, b, false));
+    }
+
+    #[test]
+    fn transposed_unicode() {
+        let a = "شاهنامه";
+        let b = "شهانامه";
+        assert_eq!(damerau_distance(a, b, false), 1);
+    }
+
+    #[test]
+    fn empty_and_equal() {
+        assert_eq!(damerau_distance("maḥmūd", "", false), 6);
+        assert_eq!(damerau_distance("", "levenshtein", true), 11);
+        assert_eq!(damerau_distance("Ghiyāth", "Ghiyāth", false), 0);
+    }
+}
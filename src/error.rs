@@ -0,0 +1,114 @@
+use core::fmt;
+
+/// The error returned by `try_edit_distance_ascii` when either string contains
+/// non-ASCII characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonAsciiError;
+
+impl fmt::Display for NonAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("string is not ASCII-only")
+    }
+}
+
+impl core::error::Error for NonAsciiError {}
+
+/// The error returned by `edit_distance_limited` when either string is longer than the
+/// limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthExceeded {
+    /// The length of the longer string (in bytes for ASCII, `char`s otherwise)
+    pub len: usize,
+    /// The limit it exceeds
+    pub max_len: usize,
+}
+
+impl fmt::Display for LengthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string length {} exceeds the limit of {}",
+            self.len, self.max_len
+        )
+    }
+}
+
+impl core::error::Error for LengthExceeded {}
+
+/// The error returned by `apply_script` when an edit script doesn't fit the string it is
+/// applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScriptMismatch {
+    /// The index of the first operation that doesn't fit, or the length of the script if
+    /// it ends before the string does
+    pub index: usize,
+}
+
+impl fmt::Display for ScriptMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "edit script does not match the string at operation {}",
+            self.index
+        )
+    }
+}
+
+impl core::error::Error for ScriptMismatch {}
+
+/// The error returned by `LevAutomaton::try_new` when the automaton would have more
+/// states than the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateLimitExceeded {
+    /// The limit on the number of states
+    pub max_states: usize,
+}
+
+impl fmt::Display for StateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "automaton would need more than {} states",
+            self.max_states
+        )
+    }
+}
+
+impl core::error::Error for StateLimitExceeded {}
+
+/// The error returned by `try_edit_distance` when a comparison can't give a meaningful
+/// result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditError {
+    /// The configuration combines options that contradict each other, i.e., ASCII mode
+    /// with Unicode normalization; this can only occur with the `normalization` feature
+    IncompatibleOptions,
+    /// ASCII mode is set, but either string contains non-ASCII characters
+    NonAsciiInput,
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncompatibleOptions => f.write_str("incompatible options"),
+            Self::NonAsciiInput => f.write_str("string is not ASCII-only"),
+        }
+    }
+}
+
+impl core::error::Error for EditError {}
+
+/// The error returned by `decode_diff` when a string is not a valid encoded diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    /// The byte offset at which the encoding goes wrong
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid diff at byte {}", self.offset)
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,83 @@
+//! C bindings for the core function, enabled by the `ffi` feature
+
+#![allow(unsafe_code)]
+
+use core::ffi::{CStr, c_char};
+
+use crate::{edit_distance, edit_distance_bytes};
+
+/// Returns the Levenshtein distance between two null-terminated C strings, `a` and `b`,
+/// or `u32::MAX` if it can't be computed. The `ascii` flag works as for `edit_distance`.
+///
+/// In ASCII mode, the strings are compared byte by byte, whatever their encoding.
+/// Otherwise they must be valid UTF-8, and `u32::MAX` is returned if either is not. It
+/// is also returned if either pointer is null.
+///
+/// # Safety
+///
+/// Each of `a` and `b` must be either null or a pointer to a valid null-terminated
+/// string, which must not be modified for the duration of the call.
+#[unsafe(no_mangle)]
+#[must_use]
+pub unsafe extern "C" fn levenshtein_distance(
+    a: *const c_char,
+    b: *const c_char,
+    ascii: bool,
+) -> u32 {
+    if a.is_null() || b.is_null() {
+        return u32::MAX;
+    }
+
+    // SAFETY: neither pointer is null, and the caller guarantees the rest
+    let (a, b) = unsafe { (CStr::from_ptr(a), CStr::from_ptr(b)) };
+
+    if ascii {
+        return edit_distance_bytes(a.to_bytes(), b.to_bytes());
+    }
+
+    match (a.to_str(), b.to_str()) {
+        (Ok(a), Ok(b)) => edit_distance(a, b, false),
+        _ => u32::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::ffi::CString;
+    use core::ptr;
+
+    #[test]
+    fn round_trip() {
+        let a = CString::new("maḥmūd").unwrap();
+        let b = CString::new("muḥammad").unwrap();
+        let dist = unsafe { levenshtein_distance(a.as_ptr(), b.as_ptr(), false) };
+        assert_eq!(dist, edit_distance("maḥmūd", "muḥammad", false));
+
+        let dist = unsafe { levenshtein_distance(a.as_ptr(), b.as_ptr(), true) };
+        assert_eq!(dist, edit_distance("maḥmūd", "muḥammad", true));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let a = CString::new(b"caf\xE9".to_vec()).unwrap();
+        let b = CString::new("café").unwrap();
+        assert_eq!(
+            unsafe { levenshtein_distance(a.as_ptr(), b.as_ptr(), false) },
+            u32::MAX
+        );
+        assert_eq!(
+            unsafe { levenshtein_distance(a.as_ptr(), b.as_ptr(), true) },
+            2
+        );
+    }
+
+    #[test]
+    fn null_pointer() {
+        let a = CString::new("kitten").unwrap();
+        assert_eq!(
+            unsafe { levenshtein_distance(a.as_ptr(), ptr::null(), true) },
+            u32::MAX
+        );
+    }
+}
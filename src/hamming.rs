@@ -0,0 +1,55 @@
+/// Returns the Hamming distance between two strings, `a` and `b`, or `None` if their
+/// lengths differ. The `ascii` flag indicates whether the strings can be treated as
+/// ASCII-only.
+///
+/// This is the number of positions at which the strings differ, with lengths and
+/// positions counted in bytes for ASCII, `char`s otherwise. It only
+/// counts substitutions, but it runs in linear time with no allocation.
+#[must_use]
+pub fn hamming_distance(a: &str, b: &str, ascii: bool) -> Option<u32> {
+    if ascii {
+        hamming(a.bytes(), b.bytes())
+    } else {
+        hamming(a.chars(), b.chars())
+    }
+}
+
+fn hamming<T: PartialEq>(
+    mut a: impl Iterator<Item = T>,
+    mut b: impl Iterator<Item = T>,
+) -> Option<u32> {
+    let mut dist = 0;
+
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => dist += u32::from(x != y),
+            (None, None) => return Some(dist),
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn karolin_kathrin() {
+        assert_eq!(hamming_distance("karolin", "kathrin", true), Some(3));
+        assert_eq!(hamming_distance("karolin", "kathrin", false), Some(3));
+    }
+
+    #[test]
+    fn length_mismatch() {
+        assert_eq!(hamming_distance("karolin", "karoline", true), None);
+        assert_eq!(hamming_distance("", "a", false), None);
+        assert_eq!(hamming_distance("", "", false), Some(0));
+    }
+
+    #[test]
+    fn unicode_lengths() {
+        // Same number of chars, but not of bytes
+        assert_eq!(hamming_distance("maḥmūd", "mahmud", false), Some(2));
+        assert_eq!(hamming_distance("maḥmūd", "mahmud", true), None);
+    }
+}
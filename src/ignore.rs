@@ -0,0 +1,90 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::edit_distance;
+use crate::prep::{NormalizeOpts, normalize};
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, after
+/// removing every character in `ignore` from both. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// This is useful for disregarding, e.g., punctuation and whitespace, so that "don't"
+/// matches "dont". If `ignore` is sorted, it is searched by bisection; otherwise
+/// linearly, which is just as fast for a handful of characters.
+#[must_use]
+pub fn edit_distance_ignoring(a: &str, b: &str, ignore: &[char], ascii: bool) -> u32 {
+    let ignored: &dyn Fn(char) -> bool = if ignore.is_sorted() {
+        &|c| ignore.binary_search(&c).is_ok()
+    } else {
+        &|c| ignore.contains(&c)
+    };
+
+    edit_distance(&remove(a, ignored), &remove(b, ignored), ascii)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, after
+/// replacing each run of whitespace in both with a single space. The `ascii` flag works
+/// as for `edit_distance`.
+///
+/// Whitespace here is any `char` for which `char::is_whitespace` holds, so a difference
+/// in the amount of space between two words doesn't count, though a space where there
+/// was none still does. Everything else is compared exactly.
+#[must_use]
+pub fn edit_distance_collapse_ws(a: &str, b: &str, ascii: bool) -> u32 {
+    let opts = NormalizeOpts {
+        collapse_whitespace: true,
+        ..NormalizeOpts::default()
+    };
+    edit_distance(&normalize(a, opts), &normalize(b, opts), ascii)
+}
+
+/// `s` without the characters for which `ignored` is true, allocating only if there are
+/// any
+fn remove<'s>(s: &'s str, ignored: &dyn Fn(char) -> bool) -> Cow<'s, str> {
+    if s.chars().any(ignored) {
+        Cow::Owned(s.chars().filter(|&c| !ignored(c)).collect::<String>())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_ignored() {
+        assert_eq!(
+            edit_distance_ignoring("a. b.c", "abc", &['.', ' '], true),
+            0
+        );
+        assert_eq!(edit_distance_ignoring("don't", "dont", &['\''], false), 0);
+        assert_eq!(edit_distance_ignoring("a. b.c", "abc", &[], true), 3);
+    }
+
+    #[test]
+    fn sorted_or_not() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "Ali ibn Abi Talib";
+        let ignore = ['ʿ', ' ', '-'];
+        let mut sorted = ignore;
+        sorted.sort_unstable();
+
+        assert_eq!(
+            edit_distance_ignoring(a, b, &ignore, false),
+            edit_distance_ignoring(a, b, &sorted, false)
+        );
+        assert_eq!(edit_distance_ignoring(a, b, &ignore, false), 4);
+    }
+
+    #[test]
+    fn whitespace_collapsed() {
+        assert_eq!(edit_distance_collapse_ws("a   b", "a b", true), 0);
+        assert_eq!(edit_distance_collapse_ws("a b", "ab", true), 1);
+        assert_eq!(edit_distance_collapse_ws("a\t\n b", "a b", false), 0);
+        assert_eq!(
+            edit_distance_collapse_ws("ʿAlī  ibn\tAbī", "ʿAli ibn Abi", false),
+            2
+        );
+    }
+}
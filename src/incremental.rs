@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+/// An edit distance to a fixed target string, updated as characters are appended to the
+/// query, e.g., as a user types into a search box
+///
+/// ```
+/// use agnostic_levenshtein::IncrementalDistance;
+///
+/// let mut inc = IncrementalDistance::new("sitting");
+/// for c in "kitten".chars() {
+///     inc.push_char(c);
+/// }
+/// assert_eq!(inc.distance(), 3);
+/// ```
+///
+/// Each `push_char` computes a single new row of the DP matrix from the stored previous
+/// one, in time proportional to the length of the target. Strings are compared as
+/// sequences of `char`s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncrementalDistance {
+    target: Vec<char>,
+    // Distance from the query so far to each prefix of the target
+    row: Vec<u32>,
+}
+
+impl IncrementalDistance {
+    /// Creates a comparator against `target`, with an empty query
+    #[must_use]
+    pub fn new(target: &str) -> Self {
+        let target: Vec<char> = target.chars().collect();
+        let row = (0..=target.len() as u32).collect();
+        Self { target, row }
+    }
+
+    /// Appends `c` to the query, updating the distance
+    pub fn push_char(&mut self, c: char) {
+        // The value of the previous row in the column before `j`
+        let mut diag = self.row[0];
+        self.row[0] += 1;
+
+        for j in 1..=self.target.len() {
+            let above = self.row[j];
+
+            self.row[j] = if self.target[j - 1] == c {
+                diag
+            } else {
+                let insert = self.row[j - 1] + 1;
+                let delete = above + 1;
+                let substitute = diag + 1;
+
+                insert.min(delete).min(substitute)
+            };
+
+            diag = above;
+        }
+    }
+
+    /// Returns the distance between the query so far and the target
+    #[must_use]
+    pub fn distance(&self) -> u32 {
+        self.row[self.target.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn kitten_sitting() {
+        let mut inc = IncrementalDistance::new("sitting");
+        assert_eq!(inc.distance(), 7);
+
+        for c in "kitten".chars() {
+            inc.push_char(c);
+        }
+        assert_eq!(inc.distance(), 3);
+    }
+
+    #[test]
+    fn every_prefix() {
+        let query = "Farīd al-Dīn ʿAṭṭār";
+        let target = "Farid ud-Din Attar";
+        let mut inc = IncrementalDistance::new(target);
+
+        for (i, c) in query.char_indices() {
+            inc.push_char(c);
+            let prefix = &query[..i + c.len_utf8()];
+            assert_eq!(inc.distance(), edit_distance(prefix, target, false));
+        }
+    }
+
+    #[test]
+    fn empty_target() {
+        let mut inc = IncrementalDistance::new("");
+        inc.push_char('ā');
+        inc.push_char('b');
+        assert_eq!(inc.distance(), 2);
+    }
+}
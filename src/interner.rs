@@ -0,0 +1,149 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{edit_distance_bytes, edit_distance_slice};
+
+/// A mapping of string tokens to `u32` IDs, for use with `edit_distance_interned`
+///
+/// ```
+/// use agnostic_levenshtein::{Interner, edit_distance_interned};
+///
+/// let mut interner = Interner::new();
+/// let a = interner.intern_all("the cat sat on the mat".split(' '));
+/// let b = interner.intern_all("the cat lay on a mat".split(' '));
+/// assert_eq!(edit_distance_interned(&a, &b), 2);
+/// ```
+///
+/// IDs are assigned in order of first appearance, starting from 0, so sequences interned
+/// by the same interner can be compared by ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Interner {
+    ids: BTreeMap<String, u32>,
+    // Each token, indexed by its ID
+    tokens: Vec<String>,
+}
+
+impl Interner {
+    /// Creates an empty interner
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ids: BTreeMap::new(),
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Returns the ID of `token`, assigning the next one if it's new
+    pub fn intern(&mut self, token: &str) -> u32 {
+        if let Some(&id) = self.ids.get(token) {
+            return id;
+        }
+
+        let id = self.tokens.len() as u32;
+        self.ids.insert(String::from(token), id);
+        self.tokens.push(String::from(token));
+        id
+    }
+
+    /// Returns the IDs of `tokens`, in order, interning any that are new
+    pub fn intern_all<'t>(&mut self, tokens: impl IntoIterator<Item = &'t str>) -> Vec<u32> {
+        tokens.into_iter().map(|token| self.intern(token)).collect()
+    }
+
+    /// Returns the ID of `token`, if it has been interned
+    #[must_use]
+    pub fn get(&self, token: &str) -> Option<u32> {
+        self.ids.get(token).copied()
+    }
+
+    /// Returns the token with the given ID, if any
+    #[must_use]
+    pub fn resolve(&self, id: u32) -> Option<&str> {
+        self.tokens.get(id as usize).map(String::as_str)
+    }
+
+    /// Returns the number of distinct tokens interned
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns `true` if no tokens have been interned
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+/// Returns the Levenshtein distance (`u32`) between two sequences of interned token IDs,
+/// `a` and `b`, as from `Interner::intern_all`
+///
+/// This is `edit_distance_slice`, except that if every ID in both sequences is below 256
+/// (i.e., at most 256 distinct tokens have been interned), they are narrowed to bytes,
+/// so as to use the bit-parallel algorithms of `edit_distance_bytes`.
+#[must_use]
+pub fn edit_distance_interned(a: &[u32], b: &[u32]) -> u32 {
+    let narrow =
+        |ids: &[u32]| -> Option<Vec<u8>> { ids.iter().map(|&id| u8::try_from(id).ok()).collect() };
+
+    match (narrow(a), narrow(b)) {
+        (Some(a_bytes), Some(b_bytes)) => edit_distance_bytes(&a_bytes, &b_bytes),
+        _ => edit_distance_slice(a, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interned_matches_strings() {
+        let sentences = [
+            "in the name of god the merciful the compassionate",
+            "praise be to god lord of the worlds",
+            "the merciful the compassionate",
+            "",
+            "master of the day of judgment",
+        ];
+
+        let mut interner = Interner::new();
+        for a in sentences {
+            for b in sentences {
+                let a_tokens: Vec<&str> = a.split_whitespace().collect();
+                let b_tokens: Vec<&str> = b.split_whitespace().collect();
+                let a_ids = interner.intern_all(a_tokens.iter().copied());
+                let b_ids = interner.intern_all(b_tokens.iter().copied());
+                assert_eq!(
+                    edit_distance_interned(&a_ids, &b_ids),
+                    edit_distance_slice(&a_tokens, &b_tokens)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wide_ids() {
+        // More than 256 distinct tokens, so IDs no longer fit in a byte
+        let mut interner = Interner::new();
+        let words: Vec<String> = (0..300).map(|i| alloc::format!("w{i}")).collect();
+        let a = interner.intern_all(words.iter().map(String::as_str));
+        let b = interner.intern_all(words.iter().rev().map(String::as_str));
+        assert_eq!(interner.len(), 300);
+        assert_eq!(edit_distance_interned(&a, &b), edit_distance_slice(&a, &b));
+        assert_eq!(edit_distance_interned(&a[..10], &a[1..10]), 1);
+    }
+
+    #[test]
+    fn lookup() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        assert_eq!(interner.intern("kitten"), 0);
+        assert_eq!(interner.intern("sitting"), 1);
+        assert_eq!(interner.intern("kitten"), 0);
+        assert_eq!(interner.get("sitting"), Some(1));
+        assert_eq!(interner.get("mitten"), None);
+        assert_eq!(interner.resolve(1), Some("sitting"));
+        assert_eq!(interner.resolve(2), None);
+    }
+}
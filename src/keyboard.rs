@@ -0,0 +1,94 @@
+use crate::edit_distance_with;
+
+/// Returns the edit distance (`u32`) between two strings, `a` and `b`, with substitution
+/// costs reflecting the US QWERTY keyboard layout, for weighing typing errors.
+///
+/// Substituting a key for one adjacent to it (e.g., `s` for `d`) costs 1, and any other
+/// substitution costs 2; insertions and deletions cost 1. Letters are matched to keys
+/// regardless of case, so `a` for `A` also counts as adjacent. This is a preset of
+/// `edit_distance_with`.
+#[must_use]
+pub fn qwerty_distance(a: &str, b: &str) -> u32 {
+    edit_distance_with(a, b, |x, y| if adjacent(x, y) { 1 } else { 2 })
+}
+
+/// Whether `x` and `y` are on the same or neighboring keys
+fn adjacent(x: char, y: char) -> bool {
+    let (x, y) = (x.to_ascii_lowercase(), y.to_ascii_lowercase());
+    if x == y {
+        return x.is_ascii_alphanumeric();
+    }
+
+    QWERTY_NEIGHBORS
+        .binary_search_by_key(&x, |&(key, _)| key)
+        .is_ok_and(|idx| QWERTY_NEIGHBORS[idx].1.contains(y))
+}
+
+/// The keys bordering each letter and digit key, sorted by key
+const QWERTY_NEIGHBORS: [(char, &str); 36] = [
+    ('0', "9op"),
+    ('1', "2q"),
+    ('2', "13qw"),
+    ('3', "24ew"),
+    ('4', "35er"),
+    ('5', "46rt"),
+    ('6', "57ty"),
+    ('7', "68uy"),
+    ('8', "79iu"),
+    ('9', "08io"),
+    ('a', "qswz"),
+    ('b', "ghnv"),
+    ('c', "dfvx"),
+    ('d', "cefrsx"),
+    ('e', "34drsw"),
+    ('f', "cdgrtv"),
+    ('g', "bfhtvy"),
+    ('h', "bgjnuy"),
+    ('i', "89jkou"),
+    ('j', "hikmnu"),
+    ('k', "ijlmo"),
+    ('l', "kop"),
+    ('m', "jkn"),
+    ('n', "bhjm"),
+    ('o', "09iklp"),
+    ('p', "0lo"),
+    ('q', "12aw"),
+    ('r', "45deft"),
+    ('s', "adewxz"),
+    ('t', "56fgry"),
+    ('u', "78hijy"),
+    ('v', "bcfg"),
+    ('w', "23aeqs"),
+    ('x', "cdsz"),
+    ('y', "67ghtu"),
+    ('z', "asx"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacent_cheaper() {
+        assert_eq!(qwerty_distance("cat", "car"), 1);
+        assert_eq!(qwerty_distance("cat", "cam"), 2);
+        assert!(qwerty_distance("cat", "car") < qwerty_distance("cat", "cam"));
+    }
+
+    #[test]
+    fn neighbors_symmetric() {
+        for (key, neighbors) in QWERTY_NEIGHBORS {
+            for neighbor in neighbors.chars() {
+                assert!(adjacent(neighbor, key), "{neighbor} / {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn case_and_indels() {
+        assert_eq!(qwerty_distance("Cat", "cat"), 1);
+        assert_eq!(qwerty_distance("cat", "cart"), 1);
+        assert_eq!(qwerty_distance("", "cat"), 3);
+        assert_eq!(qwerty_distance("ā", "a"), 2);
+    }
+}
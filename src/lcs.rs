@@ -0,0 +1,81 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+/// Returns the length of the longest common subsequence of two strings, `a` and `b`.
+/// The `ascii` flag indicates whether the strings can be treated as ASCII-only.
+///
+/// This is the basis of diff-style comparison: the number of insertions and deletions
+/// (with no substitutions) needed to turn `a` into `b` is `len(a) + len(b) - 2 * lcs`,
+/// with lengths in bytes for ASCII, `char`s otherwise.
+#[must_use]
+pub fn lcs_length(a: &str, b: &str, ascii: bool) -> u32 {
+    if ascii {
+        if a == b {
+            return a.len() as u32;
+        }
+        max_common(a.as_bytes(), b.as_bytes())
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        if a == b {
+            return a_chars.len() as u32;
+        }
+        let b_chars: Vec<char> = b.chars().collect();
+        max_common(&a_chars, &b_chars)
+    }
+}
+
+fn max_common<T: PartialEq>(a: &[T], b: &[T]) -> u32 {
+    let m = a.len();
+
+    // Nothing in common with an empty prefix of `b`
+    let mut dp_prev: Vec<u32> = vec![0; m + 1];
+    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+
+    for b_char in b {
+        for j in 1..=m {
+            dp_curr[j] = if a[j - 1] == *b_char {
+                dp_prev[j - 1] + 1
+            } else {
+                dp_prev[j].max(dp_curr[j - 1])
+            };
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn textbook_example() {
+        assert_eq!(lcs_length("ABCBDAB", "BDCAB", true), 4);
+        assert_eq!(lcs_length("ABCBDAB", "BDCAB", false), 4);
+    }
+
+    #[test]
+    fn identical() {
+        assert_eq!(lcs_length("levenshtein", "levenshtein", true), 11);
+        assert_eq!(lcs_length("maḥmūd", "maḥmūd", false), 6);
+        assert_eq!(lcs_length("maḥmūd", "maḥmūd", true), 9);
+    }
+
+    #[test]
+    fn indel_distance() {
+        let (a, b) = ("sitting", "kitten");
+        let lcs = lcs_length(a, b, true);
+        assert_eq!(lcs, 4);
+        // Two substitutions and an insertion become five insertions and deletions
+        assert_eq!(a.len() as u32 + b.len() as u32 - 2 * lcs, 5);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(lcs_length("", "abc", true), 0);
+        assert_eq!(lcs_length("شهنامه", "", false), 0);
+    }
+}
@@ -1,64 +1,830 @@
 //! This library provides a common algorithm for calculating the Levenshtein distance
 //! between two strings, i.e., the minimum number of single-character edits (insertions,
-//! deletions, or substitutions) required to change one string into the other. There is
-//! a single public function, `edit_distance`, which takes two string references
+//! deletions, or substitutions) required to change one string into the other. The main
+//! public function, `edit_distance`, takes two string references
 //! (`&str`) and a `bool` flag indicating whether the strings can be treated as
 //! ASCII-only. If the flag is set to false—the safer option—the strings will operated
 //! on as sequences of `char`s, i.e., 32-bit Unicode scalar values. This does involve
 //! more allocation and probably a longer running time than the ASCII case. The return
 //! value of `edit_distance`, in any event, is the Levenshtein distance as `u32`.
+//!
+//! A number of related measures are provided alongside it, following the same
+//! conventions—e.g., `damerau_distance`, which also counts the transposition of two
+//! adjacent characters as a single edit.
+//!
+//! The crate supports `no_std` environments with `alloc`: disable the default `std`
+//! feature to build without the standard library. The optional `normalization` feature
+//! enables comparison after Unicode NFC normalization, via `edit_distance_normalized`,
+//! or without diacritics, via `edit_distance_ascii_fold`;
+//! `segmentation`, comparison of grapheme clusters, via `edit_distance_graphemes`;
+//! `casefold`, comparison after full Unicode case folding, via `edit_distance_casefold`
+//! (with `std`);
+//! `rayon`, multithreaded batch comparison, via `edit_distance_batch_parallel`; and
+//! `ffi`, a C binding of the core function, via `levenshtein_distance`. With `serde`,
+//! `EditOp` and `DistanceResult` can be serialized; and with `wasm`, the core function
+//! is exported to JavaScript, via `edit_distance_wasm`.
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![deny(unsafe_code)]
 #![deny(missing_docs)]
 #![warn(clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::cast_possible_truncation)]
+// `f64::mul_add` is unavailable without `std`
+#![allow(clippy::suboptimal_flops)]
 
-use std::mem::swap;
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::mem::swap;
+use core::ops::Add;
+
+mod affine;
+mod alignment;
+mod anagram;
+mod automaton;
+mod banded;
+mod batch;
+mod bktree;
+mod builder;
+mod case;
+mod classes;
+mod comparable;
+mod constant;
+mod damerau;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod hamming;
+mod ignore;
+mod incremental;
+mod interner;
+mod keyboard;
+mod lcs;
+#[cfg(feature = "std")]
+mod memo;
+mod myers;
+#[cfg(feature = "normalization")]
+mod normalize;
+#[cfg(feature = "std")]
+mod os;
+mod phonetic;
+mod prep;
+mod rules;
+mod script;
+#[cfg(feature = "segmentation")]
+mod segment;
+mod sift4;
+#[cfg(feature = "std")]
+mod stream;
+mod substring;
+mod swar;
+mod symspell;
+#[cfg(test)]
+mod test_util;
+mod typo;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weighted;
+mod wildcard;
+mod word;
+
+pub mod similarity;
+
+pub use affine::affine_distance;
+pub use alignment::alignment_score;
+pub use anagram::anagram_distance;
+pub use automaton::LevAutomaton;
+pub use banded::banded_distance;
+#[cfg(feature = "std")]
+pub use batch::distances_from_reader;
+#[cfg(feature = "rayon")]
+pub use batch::edit_distance_batch_parallel;
+pub use batch::{
+    closest_match, closest_match_case_insensitive, distances, edit_distance_batch,
+    edit_distance_batch_case_insensitive, k_nearest, pairwise_distances, suggest, within_distance,
+};
+pub use bktree::BkTree;
+pub use builder::{Levenshtein, try_edit_distance};
+pub use case::edit_distance_case_insensitive;
+#[cfg(feature = "casefold")]
+pub use case::edit_distance_casefold;
+pub use classes::{CharClass, edit_distance_classes};
+pub use comparable::{EditComparable, edit_distance_by};
+pub use constant::edit_distance_const;
+pub use damerau::{damerau_distance, osa_distance};
+pub use error::{
+    EditError, LengthExceeded, NonAsciiError, ParseError, ScriptMismatch, StateLimitExceeded,
+};
+#[cfg(feature = "ffi")]
+pub use ffi::levenshtein_distance;
+pub use hamming::hamming_distance;
+pub use ignore::{edit_distance_collapse_ws, edit_distance_ignoring};
+pub use incremental::IncrementalDistance;
+pub use interner::{Interner, edit_distance_interned};
+pub use keyboard::qwerty_distance;
+pub use lcs::lcs_length;
+#[cfg(feature = "std")]
+pub use memo::MemoizedDistance;
+#[cfg(feature = "normalization")]
+pub use normalize::{edit_distance_ascii_fold, edit_distance_normalized};
+#[cfg(feature = "std")]
+pub use os::edit_distance_os;
+pub use phonetic::{phonetic_distance, soundex};
+pub use rules::edit_distance_with_rules;
+pub use script::{
+    Breakdown, DistanceResult, EditOp, TieBreak, align, apply_script, decode_diff, distance_matrix,
+    edit_distance_breakdown, edit_positions, edit_script, edit_script_with, encode_diff,
+    format_alignment, index_map,
+};
+#[cfg(feature = "segmentation")]
+pub use segment::edit_distance_graphemes;
+#[cfg(all(feature = "normalization", feature = "segmentation"))]
+pub use segment::edit_distance_visual;
+pub use sift4::sift4_distance;
+#[cfg(feature = "std")]
+pub use stream::edit_distance_streaming;
+pub use substring::best_substring_match;
+pub use symspell::SymSpellIndex;
+pub use typo::{Context, TypoModel, UniformModel, weighted_suggest};
+#[cfg(feature = "wasm")]
+pub use wasm::edit_distance_wasm;
+#[cfg(feature = "std")]
+pub use weighted::idf_weighted_distance;
+pub use weighted::{Weights, edit_distance_with, prefix_weighted_distance, weighted_distance};
+pub use wildcard::edit_distance_wildcard;
+pub use word::{word_anagram_distance, word_distance};
+
+use myers::myers_distance;
+use swar::blocked_distance;
 
 /// Returns the Levenshtein distance (`u32`) between two strings (`&str`), `a` and `b`.
 /// The `ascii` flag indicates whether the strings can be treated as ASCII-only.
+///
+/// Lengths and distances are computed as `u32`, so the result is not meaningful if
+/// either string is longer than `u32::MAX` units (bytes or `char`s, depending on the
+/// mode). Use `edit_distance_checked` if that's a concern.
 #[must_use]
 pub fn edit_distance(a: &str, b: &str, ascii: bool) -> u32 {
     // Handle edge cases as early as possible
+    if let Some(dist) = edge_case(a, b, ascii) {
+        return dist;
+    }
+
+    if ascii {
+        bytes_distance(a.as_bytes(), b.as_bytes())
+    } else {
+        let (a, b) = trim_str_affixes(a, b);
+
+        // Only the shorter string is collected; the longer one is streamed
+        let (a_len, b_len) = (a.chars().count(), b.chars().count());
+        let (short, long, long_len) = if a_len <= b_len {
+            (a, b, b_len)
+        } else {
+            (b, a, a_len)
+        };
+
+        // As in `trimmed_shortcut`
+        let mut short_chars = short.chars();
+        match (short_chars.next(), short_chars.next()) {
+            (None, _) => return long_len as u32,
+            (Some(c), None) => return long_len as u32 - u32::from(long.contains(c)),
+            _ => {}
+        }
+
+        let short_chars: Vec<char> = short.chars().collect();
+
+        min_distance_compact(&short_chars, long.chars(), long_len)
+    }
+}
+
+/// Returns the Levenshtein distance (`u32`) between two byte slices, `a` and `b`, which
+/// need not be valid UTF-8.
+///
+/// This is the same computation as the ASCII mode of `edit_distance`: each byte counts
+/// as one unit, so a multibyte UTF-8 sequence counts as several.
+#[must_use]
+pub fn edit_distance_bytes(a: &[u8], b: &[u8]) -> u32 {
     if a == b {
         return 0;
     }
 
-    if a.is_empty() {
-        if ascii {
-            return b.len() as u32;
-        }
-        return b.chars().count() as u32;
+    bytes_distance(a, b)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two byte slices, `a` and `b`, using
+/// the caller's buffers for the DP rows, so that repeated calls need not allocate.
+///
+/// The result is the same as from `edit_distance_bytes`. `prev` and `curr` may be of any
+/// length to begin with. If the shorter input is longer than 64 bytes (after any shared
+/// prefix and suffix are trimmed), their contents are overwritten, and they are resized
+/// to one more than its length, which allocates only if they lack the capacity;
+/// otherwise, the distance is computed without them. Reusing the same pair across calls
+/// thus allocates only when a longer input than before comes along.
+pub fn edit_distance_ascii_buf(
+    a: &[u8],
+    b: &[u8],
+    prev: &mut Vec<u32>,
+    curr: &mut Vec<u32>,
+) -> u32 {
+    let (a, b) = trim_affixes(a, b);
+    if let Some(dist) = trimmed_shortcut(a, b) {
+        return dist;
     }
 
-    if b.is_empty() {
-        if ascii {
-            return a.len() as u32;
-        }
-        return a.chars().count() as u32;
+    // Needs no buffers at all
+    if a.len().min(b.len()) <= 64 {
+        return myers_distance(a, b);
+    }
+
+    let (a, b) = shorter_first(a, b);
+    min_distance_buffered(a, b, prev, curr)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, counted in
+/// UTF-16 code units.
+///
+/// This matches systems that measure strings in UTF-16, such as JavaScript's `length`:
+/// a character outside the Basic Multilingual Plane (e.g., most emoji) is encoded as a
+/// surrogate pair, and so counts as two units, unlike in the Unicode mode of
+/// `edit_distance`.
+#[must_use]
+pub fn edit_distance_utf16(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let a_units: Vec<u16> = a.encode_utf16().collect();
+    let b_units: Vec<u16> = b.encode_utf16().collect();
+    edit_distance_slice(&a_units, &b_units)
+}
+
+fn bytes_distance(a: &[u8], b: &[u8]) -> u32 {
+    let (a, b) = trim_affixes(a, b);
+    if let Some(dist) = trimmed_shortcut(a, b) {
+        return dist;
+    }
+
+    // Bit-parallel algorithm when the shorter string fits in a 64-bit word
+    if a.len().min(b.len()) <= 64 {
+        return myers_distance(a, b);
+    }
+
+    // Otherwise, the same over several words, with matches detected eight bytes at a time
+    let (a, b) = shorter_first(a, b);
+    blocked_distance(a, b)
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, or `None` if
+/// either is too long for the result to be computed correctly as `u32`. The `ascii` flag
+/// works as for `edit_distance`.
+///
+/// Since the distance never exceeds the length of the longer string, it's enough to
+/// check that both lengths (in bytes for ASCII, `char`s otherwise) fit in `u32`.
+#[must_use]
+pub fn edit_distance_checked(a: &str, b: &str, ascii: bool) -> Option<u32> {
+    checked_with_max(a, b, ascii, u32::MAX as usize)
+}
+
+/// `edit_distance_checked` with the largest admissible length as a parameter, so that
+/// the overflow case can be tested without gigabytes of input
+fn checked_with_max(a: &str, b: &str, ascii: bool, max_len: usize) -> Option<u32> {
+    if !(len_fits(a, ascii, max_len) && len_fits(b, ascii, max_len)) {
+        return None;
+    }
+
+    Some(edit_distance(a, b, ascii))
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, provided neither
+/// is longer than `max_len`. The `ascii` flag works as for `edit_distance`.
+///
+/// Since the computation takes time proportional to the product of the lengths, this
+/// lets a service enforce a limit on its inputs before doing any work. Lengths are in
+/// bytes for ASCII, `char`s otherwise.
+///
+/// # Errors
+///
+/// Returns `LengthExceeded`, with the length of the longer string, if it exceeds
+/// `max_len`.
+pub fn edit_distance_limited(
+    a: &str,
+    b: &str,
+    ascii: bool,
+    max_len: usize,
+) -> Result<u32, LengthExceeded> {
+    // No string has more `char`s than bytes, so there's often no need to count them
+    let within = |s: &str| s.len() <= max_len || str_len(s, ascii) <= max_len;
+    if !(within(a) && within(b)) {
+        let len = str_len(a, ascii).max(str_len(b, ascii));
+        return Err(LengthExceeded { len, max_len });
+    }
+
+    Ok(edit_distance(a, b, ascii))
+}
+
+/// Returns the Levenshtein distance between two ASCII-only strings, `a` and `b`.
+///
+/// This is the ASCII mode of `edit_distance`, but checked: with the `ascii` flag set,
+/// multibyte characters are silently compared byte by byte, which is rarely what's wanted.
+///
+/// # Errors
+///
+/// Returns `NonAsciiError` if either string contains non-ASCII characters.
+pub fn try_edit_distance_ascii(a: &str, b: &str) -> Result<u32, NonAsciiError> {
+    if !(a.is_ascii() && b.is_ascii()) {
+        return Err(NonAsciiError);
+    }
+
+    Ok(edit_distance(a, b, true))
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, if it is at most
+/// `max`; otherwise `None`. The `ascii` flag works as for `edit_distance`.
+///
+/// This can be much faster than `edit_distance` when comparing dissimilar strings, since
+/// the computation stops as soon as the distance is known to exceed `max`.
+#[must_use]
+pub fn edit_distance_within(a: &str, b: &str, ascii: bool, max: u32) -> Option<u32> {
+    if let Some(dist) = edge_case(a, b, ascii) {
+        return (dist <= max).then_some(dist);
     }
 
     if ascii {
-        min_distance(a.as_bytes(), b.as_bytes())
+        min_distance_within(a.as_bytes(), b.as_bytes(), max)
     } else {
         let a_chars: Vec<char> = a.chars().collect();
         let b_chars: Vec<char> = b.chars().collect();
-        min_distance(&a_chars, &b_chars)
+        min_distance_within(&a_chars, &b_chars, max)
     }
 }
 
-fn min_distance<T: PartialEq>(a: &[T], b: &[T]) -> u32 {
-    // We already know: strings are not equal; neither string is empty
+/// Whether the Levenshtein distance between two strings, `a` and `b`, is at most `k`. The
+/// `ascii` flag works as for `edit_distance`.
+///
+/// This is the cheapest query there is: strings whose lengths differ by more than `k`
+/// are rejected before anything is allocated, and otherwise the DP stops as soon as
+/// every value in a row exceeds `k`, as in `edit_distance_within`.
+#[must_use]
+pub fn is_within(a: &str, b: &str, k: u32, ascii: bool) -> bool {
+    if min_possible_distance(a, b, ascii) > k {
+        return false;
+    }
+
+    edit_distance_within(a, b, ascii, k).is_some()
+}
+
+/// Returns the Levenshtein distance between `query` and `candidate` if it is strictly
+/// less than `current_best`; otherwise `None`. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// This is the pruning step of a scan for the closest match: the computation stops as
+/// soon as the candidate is known not to beat the best so far. If `current_best` is 0,
+/// nothing can beat it, and the result is always `None`.
+#[must_use]
+pub fn distance_if_closer(
+    query: &str,
+    candidate: &str,
+    current_best: u32,
+    ascii: bool,
+) -> Option<u32> {
+    let max = current_best.checked_sub(1)?;
+    edit_distance_within(query, candidate, ascii, max)
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, along with the
+/// minimum of the last row of the DP matrix. The `ascii` flag works as for `edit_distance`.
+///
+/// The second value is the smallest distance between `b` and any prefix of `a` (the
+/// whole of `a` included), so it never exceeds the first. Since later rows can only
+/// grow from it, this is a bound for callers doing their own pruning—e.g., when `b` is
+/// read incrementally.
+#[must_use]
+pub fn edit_distance_with_floor(a: &str, b: &str, ascii: bool) -> (u32, u32) {
+    let mut dp_prev: Vec<u32> = Vec::new();
+    let mut dp_curr: Vec<u32> = Vec::new();
+
+    let dist = if ascii {
+        min_distance_buffered(a.as_bytes(), b.as_bytes(), &mut dp_prev, &mut dp_curr)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        min_distance_buffered(&a_chars, b.chars(), &mut dp_prev, &mut dp_curr)
+    };
+
+    // The buffers are always resized to hold at least one value
+    let floor = dp_prev.iter().copied().min().unwrap_or(dist);
+    (dist, floor)
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, choosing ASCII or
+/// Unicode mode automatically.
+///
+/// The fast byte-level path is used only if both strings are ASCII-only; otherwise they
+/// are compared as sequences of `char`s. This trades an extra scan of each string for
+/// the assurance that multibyte characters are never split.
+#[must_use]
+pub fn edit_distance_auto(a: &str, b: &str) -> u32 {
+    edit_distance(a, b, a.is_ascii() && b.is_ascii())
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, checking in debug
+/// builds that it is the same with the strings swapped. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// The distance is symmetric, but the fast paths of `edit_distance` (affix trimming,
+/// Myers' algorithm, narrower DP rows) treat the two strings differently, so swapping
+/// them exercises different code. This is meant for tests and debugging; in release
+/// builds, it is just `edit_distance`.
+///
+/// # Panics
+///
+/// Panics, with debug assertions enabled, if the two computations disagree.
+#[must_use]
+pub fn edit_distance_verified(a: &str, b: &str, ascii: bool) -> u32 {
+    let dist = edit_distance(a, b, ascii);
+    debug_assert_eq!(
+        dist,
+        edit_distance(b, a, ascii),
+        "edit distance is asymmetric for {a:?} and {b:?}"
+    );
+    dist
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, reporting progress
+/// to `on_progress` along the way. The `ascii` flag works as for `edit_distance`.
+///
+/// The DP proceeds one row per unit of the longer string, and `on_progress` is called
+/// with the fraction of rows completed about a hundred times in all, the last time with
+/// exactly 1.0. This always runs the full DP, without the fast paths of `edit_distance`,
+/// so that the rows (and thus the reports) are evenly paced. A GUI can use the callback
+/// to update a progress bar, or to yield to an event loop.
+#[must_use]
+pub fn edit_distance_with_progress<F: FnMut(f64)>(
+    a: &str,
+    b: &str,
+    ascii: bool,
+    mut on_progress: F,
+) -> u32 {
+    let dist = if ascii {
+        progress_distance(a.as_bytes(), b.as_bytes(), &mut on_progress)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        progress_distance(&a_chars, &b_chars, &mut on_progress)
+    };
+
+    on_progress(1.0);
+    dist
+}
+
+/// How many times, roughly, `edit_distance_with_progress` reports progress
+const PROGRESS_REPORTS: usize = 100;
+
+fn progress_distance<T: PartialEq>(a: &[T], b: &[T], on_progress: &mut impl FnMut(f64)) -> u32 {
+    let (a, b) = shorter_first(a, b);
+    let (m, rows) = (a.len(), b.len());
+    let step = rows.div_ceil(PROGRESS_REPORTS).max(1);
+
+    // The plain DP: unlike `min_distance_buffered`, this doesn't skip a shared prefix,
+    // which would bunch up the reports for the rows it covers
+    let mut dp_prev: Vec<u32> = (0..=m as u32).collect();
+    let mut dp_curr: Vec<u32> = alloc::vec![0; m + 1];
+
+    for (i, b_elem) in b.iter().enumerate() {
+        if i > 0 && i % step == 0 {
+            on_progress(f64::from(i as u32) / f64::from(rows as u32));
+        }
+
+        dp_curr[0] = i as u32 + 1;
+        for j in 1..=m {
+            dp_curr[j] = if a[j - 1] == *b_elem {
+                dp_prev[j - 1]
+            } else {
+                1 + dp_prev[j - 1].min(dp_prev[j]).min(dp_curr[j - 1])
+            };
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+/// Returns the similarity of two strings, `a` and `b`, as a ratio from 0.0 to 1.0. The
+/// `ascii` flag works as for `edit_distance`.
+///
+/// This is computed as `1.0 - distance / max_len`, where `max_len` is the length of the
+/// longer string (in bytes for ASCII, `char`s otherwise). Two empty strings, like any
+/// identical strings, have a ratio of 1.0. For the ratio of Python's `difflib`, which is
+/// not based on edit distance, see `similarity::difflib_ratio`.
+#[must_use]
+pub fn similarity_ratio(a: &str, b: &str, ascii: bool) -> f64 {
+    distance_and_ratio(a, b, ascii).1
+}
+
+/// Returns both the Levenshtein distance between two strings, `a` and `b`, and their
+/// similarity ratio. The `ascii` flag works as for `edit_distance`.
+///
+/// The pair is the same as from `edit_distance` and `similarity_ratio`, but the distance
+/// is computed only once.
+#[must_use]
+pub fn distance_and_ratio(a: &str, b: &str, ascii: bool) -> (u32, f64) {
+    let max_len = str_len(a, ascii).max(str_len(b, ascii)) as u32;
+    if max_len == 0 {
+        return (0, 1.0);
+    }
+
+    let dist = edit_distance(a, b, ascii);
+    (dist, 1.0 - f64::from(dist) / f64::from(max_len))
+}
+
+/// Returns the similarity ratio of two strings, `a` and `b`, as an exact fraction
+/// `(numerator, denominator)`. The `ascii` flag works as for `edit_distance`.
+///
+/// This is `(max_len - distance, max_len)`, the fraction that `similarity_ratio` rounds
+/// to an `f64`, unreduced, so fractions can be compared exactly (by cross-multiplying)
+/// or formatted as the caller wishes. Two empty strings give `(1, 1)` rather than
+/// `(0, 0)`, so the denominator is never zero.
+#[must_use]
+pub fn similarity_fraction(a: &str, b: &str, ascii: bool) -> (u32, u32) {
+    let max_len = str_len(a, ascii).max(str_len(b, ascii)) as u32;
+    if max_len == 0 {
+        return (1, 1);
+    }
+
+    (max_len - edit_distance(a, b, ascii), max_len)
+}
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, if their similarity
+/// ratio is at least `min_ratio`; otherwise `None`. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// This is `edit_distance_within` with a threshold that scales with the length of the
+/// longer string: a `min_ratio` of 0.7, e.g., rejects pairs in which more than 30% of
+/// the characters differ. The ratio is as computed by `similarity_ratio`.
+#[must_use]
+pub fn edit_distance_within_ratio(a: &str, b: &str, ascii: bool, min_ratio: f64) -> Option<u32> {
+    let max_len = str_len(a, ascii).max(str_len(b, ascii)) as u32;
+    if max_len == 0 {
+        return (min_ratio <= 1.0).then_some(0);
+    }
+
+    let passes = |dist: u32| 1.0 - f64::from(dist) / f64::from(max_len) >= min_ratio;
+
+    // Estimate the largest distance that passes, then correct for any rounding error
+    let estimate = ((1.0 - min_ratio) * f64::from(max_len)).clamp(0.0, f64::from(max_len));
+    #[allow(clippy::cast_sign_loss)]
+    let mut max = estimate as u32;
+    if max < max_len && passes(max + 1) {
+        max += 1;
+    } else if !passes(max) {
+        max = max.checked_sub(1)?;
+    }
+
+    edit_distance_within(a, b, ascii, max)
+}
+
+/// Returns a lower bound on the Levenshtein distance between two strings, `a` and `b`:
+/// the difference in their lengths. The `ascii` flag works as for `edit_distance`.
+///
+/// Lengths are counted in bytes for ASCII, `char`s otherwise, so this takes at most a
+/// linear scan, with no allocation. Callers with a distance threshold can use it to
+/// reject candidates before computing the exact distance.
+#[must_use]
+pub fn min_possible_distance(a: &str, b: &str, ascii: bool) -> u32 {
+    str_len(a, ascii).abs_diff(str_len(b, ascii)) as u32
+}
+
+/// Returns the Levenshtein distance (`u32`) between two sequences of `char`s, `a` and
+/// `b`, given as iterators.
+///
+/// This is the same as the Unicode mode of `edit_distance`, for `char`s that don't come
+/// from a `&str`. One sequence is collected, and the other streamed; both are consumed.
+/// The one collected is whichever looks shorter from its `size_hint`, since that bounds
+/// the memory used.
+#[must_use]
+pub fn edit_distance_chars<A: Iterator<Item = char>, B: Iterator<Item = char>>(a: A, b: B) -> u32 {
+    let a_max = a.size_hint().1.unwrap_or(usize::MAX);
+    let b_max = b.size_hint().1.unwrap_or(usize::MAX);
+
+    if a_max <= b_max {
+        let a_chars: Vec<char> = a.collect();
+        min_distance(&a_chars, b)
+    } else {
+        let b_chars: Vec<char> = b.collect();
+        min_distance(&b_chars, a)
+    }
+}
+
+/// Returns the Levenshtein distance (`u32`) between two slices, `a` and `b`, of any
+/// element type that can be compared for equality.
+///
+/// Each element is treated as an atomic unit, so the caller is responsible for whatever
+/// element semantics they want: bytes, `char`s, grapheme clusters, tokens, etc.
+#[must_use]
+pub fn edit_distance_slice<T: PartialEq>(a: &[T], b: &[T]) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    if a.is_empty() {
+        return b.len() as u32;
+    }
+
+    if b.is_empty() {
+        return a.len() as u32;
+    }
+
+    let (a, b) = shorter_first(a, b);
+    min_distance_compact(a, b, b.len())
+}
+
+/// Returns the distance directly if the strings are equal or either one is empty
+fn edge_case(a: &str, b: &str, ascii: bool) -> Option<u32> {
+    if a == b {
+        return Some(0);
+    }
+
+    if a.is_empty() {
+        return Some(str_len(b, ascii) as u32);
+    }
+
+    if b.is_empty() {
+        return Some(str_len(a, ascii) as u32);
+    }
+
+    None
+}
+
+/// Strips the common prefix and suffix of `a` and `b`, which contribute nothing to the
+/// distance between them
+fn trim_affixes<'x, T: PartialEq>(a: &'x [T], b: &'x [T]) -> (&'x [T], &'x [T]) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    (&a[..a.len() - suffix], &b[..b.len() - suffix])
+}
+
+/// As `trim_affixes`, but for strings, cutting only at `char` boundaries
+fn trim_str_affixes<'x>(a: &'x str, b: &'x str) -> (&'x str, &'x str) {
+    let prefix: usize = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+
+    let suffix: usize = a
+        .chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(x, y)| x == y)
+        .map(|(x, _)| x.len_utf8())
+        .sum();
+
+    (&a[..a.len() - suffix], &b[..b.len() - suffix])
+}
+
+/// The distance between `a` and `b`, with their common affixes already trimmed, if it
+/// can be found without the DP, i.e., in linear time
+///
+/// That's the case if either is empty, or has just one element: then every element of the
+/// other must be inserted but one, which is kept if it's the same and substituted
+/// otherwise. Since trimming leaves a single edit of any kind in this shape, this catches
+/// distance-1 pairs, which are common in practice (e.g., typos).
+fn trimmed_shortcut<T: PartialEq>(a: &[T], b: &[T]) -> Option<u32> {
+    let (short, long) = shorter_first(a, b);
+    match short {
+        [] => Some(long.len() as u32),
+        [x] => Some(long.len() as u32 - u32::from(long.contains(x))),
+        _ => None,
+    }
+}
+
+/// Orders `a` and `b` so that the shorter comes first. Distance is symmetric, and the DP
+/// rows are sized to the first input, so this keeps memory use to the shorter length.
+const fn shorter_first<'x, T>(a: &'x [T], b: &'x [T]) -> (&'x [T], &'x [T]) {
+    if a.len() <= b.len() { (a, b) } else { (b, a) }
+}
+
+/// Whether the length of `s` (in bytes for ASCII, `char`s otherwise) fits in `u32`
+fn len_fits(s: &str, ascii: bool, max_len: usize) -> bool {
+    // No need to count `char`s if even the byte length fits
+    s.len() <= max_len || (!ascii && s.chars().count() <= max_len)
+}
+
+/// Length of a string in bytes (ASCII mode) or `char`s
+fn str_len(s: &str, ascii: bool) -> usize {
+    if ascii { s.len() } else { s.chars().count() }
+}
+
+/// An unsigned integer type for the cells of the DP rows
+///
+/// Narrower types shrink the rows, so that more of them stay in cache, but they can only
+/// be used if no cell can overflow. Every cell is at most the length of the longer
+/// input, and it may have 1 added to it before being compared, so `u16` is safe for
+/// inputs shorter than `u16::MAX` (65,535) units.
+trait DpCell: Copy + Ord + Add<Output = Self> {
+    const ONE: Self;
+
+    /// Converts a length, which the caller ensures fits
+    fn from_len(len: usize) -> Self;
+
+    fn to_u32(self) -> u32;
+}
+
+impl DpCell for u16 {
+    const ONE: Self = 1;
+
+    fn from_len(len: usize) -> Self {
+        len as Self
+    }
+
+    fn to_u32(self) -> u32 {
+        self.into()
+    }
+}
+
+impl DpCell for u32 {
+    const ONE: Self = 1;
+
+    fn from_len(len: usize) -> Self {
+        len as Self
+    }
+
+    fn to_u32(self) -> u32 {
+        self
+    }
+}
+
+/// Whether DP rows of `u16` are safe for inputs of these lengths
+const fn fits_u16(a_len: usize, b_len: usize) -> bool {
+    a_len < u16::MAX as usize && b_len < u16::MAX as usize
+}
+
+/// `b` may be any sequence of elements (or references to them), so that it need not be
+/// materialized; only `a`, which indexes the DP rows, has to be a slice.
+fn min_distance<T: PartialEq, U: Borrow<T>>(a: &[T], b: impl IntoIterator<Item = U>) -> u32 {
+    let mut dp_prev: Vec<u32> = Vec::new();
+    let mut dp_curr: Vec<u32> = Vec::new();
+    min_distance_buffered(a, b, &mut dp_prev, &mut dp_curr)
+}
+
+/// As `min_distance`, but with rows of `u16` rather than `u32` if the lengths allow it.
+/// `b_len` is the length of `b`.
+fn min_distance_compact<T: PartialEq, U: Borrow<T>>(
+    a: &[T],
+    b: impl IntoIterator<Item = U>,
+    b_len: usize,
+) -> u32 {
+    if fits_u16(a.len(), b_len) {
+        let mut dp_prev: Vec<u16> = Vec::new();
+        let mut dp_curr: Vec<u16> = Vec::new();
+        min_distance_buffered(a, b, &mut dp_prev, &mut dp_curr)
+    } else {
+        min_distance(a, b)
+    }
+}
+
+/// As `min_distance`, but with the row buffers provided by the caller, so they can be
+/// reused across calls. Their contents are overwritten and their lengths adjusted.
+fn min_distance_buffered<T: PartialEq, U: Borrow<T>, C: DpCell>(
+    a: &[T],
+    b: impl IntoIterator<Item = U>,
+    dp_prev: &mut Vec<C>,
+    dp_curr: &mut Vec<C>,
+) -> u32 {
+    // Over a shared prefix, the DP would only ever take the match branch, converging on
+    // the same values as for the inputs without it; so skip those columns and rows
+    let mut b = b.into_iter().peekable();
+    let mut prefix = 0;
+    while prefix < a.len() && b.next_if(|c| a[prefix] == *c.borrow()).is_some() {
+        prefix += 1;
+    }
+    let a = &a[prefix..];
+
     let m = a.len();
 
     // "Previous row" is initialized with the base case:
     // the distance from an empty string to each prefix of `a`.
-    let mut dp_prev: Vec<u32> = (0..=m as u32).collect();
-    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+    dp_prev.clear();
+    dp_prev.extend((0..=m).map(C::from_len));
+    dp_curr.clear();
+    dp_curr.resize(m + 1, C::from_len(0));
+
+    for (i, b_char) in b.enumerate() {
+        let b_char = b_char.borrow();
 
-    for (i, b_char) in b.iter().enumerate() {
         // i.e., cost of deleting all chars from `b` up to this point
-        dp_curr[0] = i as u32 + 1;
+        dp_curr[0] = C::from_len(i + 1);
 
         for j in 1..=m {
             if a[j - 1] == *b_char {
@@ -66,18 +832,74 @@ fn min_distance<T: PartialEq>(a: &[T], b: &[T]) -> u32 {
                 continue;
             }
 
-            let insert = dp_curr[j - 1] + 1;
-            let delete = dp_prev[j] + 1;
-            let substitute = dp_prev[j - 1] + 1;
+            let insert = dp_curr[j - 1] + C::ONE;
+            let delete = dp_prev[j] + C::ONE;
+            let substitute = dp_prev[j - 1] + C::ONE;
 
             dp_curr[j] = insert.min(delete).min(substitute);
         }
 
         // `curr` becomes `prev` for next iteration
-        swap(&mut dp_prev, &mut dp_curr);
+        swap(dp_prev, dp_curr);
     }
 
-    dp_prev[m]
+    dp_prev[m].to_u32()
+}
+
+fn min_distance_within<T: PartialEq>(a: &[T], b: &[T], max: u32) -> Option<u32> {
+    let mut dp_prev: Vec<u32> = Vec::new();
+    let mut dp_curr: Vec<u32> = Vec::new();
+    min_distance_within_buffered(a, b, max, &mut dp_prev, &mut dp_curr)
+}
+
+/// As `min_distance_within`, but with the row buffers provided by the caller
+fn min_distance_within_buffered<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    max: u32,
+    dp_prev: &mut Vec<u32>,
+    dp_curr: &mut Vec<u32>,
+) -> Option<u32> {
+    // The distance is at least the difference in length
+    if a.len().abs_diff(b.len()) > max as usize {
+        return None;
+    }
+
+    let (a, b) = shorter_first(a, b);
+    let m = a.len();
+
+    dp_prev.clear();
+    dp_prev.extend(0..=m as u32);
+    dp_curr.clear();
+    dp_curr.resize(m + 1, 0);
+
+    for (i, b_char) in b.iter().enumerate() {
+        dp_curr[0] = i as u32 + 1;
+        let mut row_min = dp_curr[0];
+
+        for j in 1..=m {
+            if a[j - 1] == *b_char {
+                dp_curr[j] = dp_prev[j - 1];
+            } else {
+                let insert = dp_curr[j - 1] + 1;
+                let delete = dp_prev[j] + 1;
+                let substitute = dp_prev[j - 1] + 1;
+
+                dp_curr[j] = insert.min(delete).min(substitute);
+            }
+
+            row_min = row_min.min(dp_curr[j]);
+        }
+
+        // Values can only grow from one row to the next
+        if row_min > max {
+            return None;
+        }
+
+        swap(dp_prev, dp_curr);
+    }
+
+    (dp_prev[m] <= max).then_some(dp_prev[m])
 }
 
 #[cfg(test)]
@@ -121,6 +943,15 @@ mod tests {
         assert_eq!(edit_distance(a, b, true), 2);
     }
 
+    #[test]
+    fn shahnama_try_ascii() {
+        let a = "شاهنامه";
+        let b = "شهنامه";
+        assert_eq!(try_edit_distance_ascii(a, b), Err(NonAsciiError));
+        assert_eq!(try_edit_distance_ascii("sitting", b), Err(NonAsciiError));
+        assert_eq!(try_edit_distance_ascii("sitting", "kitten"), Ok(3));
+    }
+
     #[test]
     fn empty_ascii() {
         let a = "levenshtein";
@@ -141,4 +972,557 @@ mod tests {
         let b = "Ghiyāth al-Dīn";
         assert_eq!(edit_distance(a, b, true), 0);
     }
+
+    #[test]
+    fn slice_tokens() {
+        let a: Vec<u32> = vec![101, 7592, 2088, 102];
+        let b: Vec<u32> = vec![101, 7592, 2045, 2088, 102];
+        assert_eq!(edit_distance_slice(&a, &b), 1);
+        assert_eq!(edit_distance_slice(&a, &[]), 4);
+    }
+
+    #[test]
+    fn slice_matches_str() {
+        let a: Vec<char> = "sitting".chars().collect();
+        let b: Vec<char> = "kitten".chars().collect();
+        assert_eq!(
+            edit_distance_slice(&a, &b),
+            edit_distance("sitting", "kitten", false)
+        );
+    }
+
+    #[test]
+    fn within_exceeded() {
+        assert_eq!(edit_distance_within("kitten", "xyzzy", true, 2), None);
+        assert_eq!(edit_distance_within("levenshtein", "", false, 10), None);
+    }
+
+    #[test]
+    fn within_bound() {
+        assert_eq!(edit_distance_within("sitting", "kitten", true, 5), Some(3));
+        assert_eq!(edit_distance_within("sitting", "kitten", false, 3), Some(3));
+        assert_eq!(edit_distance_within("sitting", "kitten", false, 2), None);
+    }
+
+    #[test]
+    fn ratio_kitten_sitting() {
+        let ratio = similarity_ratio("kitten", "sitting", true);
+        assert!((ratio - 4.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ratio_identical_and_empty() {
+        assert!((similarity_ratio("", "", false) - 1.0).abs() < f64::EPSILON);
+        assert!((similarity_ratio("maḥmūd", "maḥmūd", false) - 1.0).abs() < f64::EPSILON);
+        assert!(similarity_ratio("maḥmūd", "", false).abs() < f64::EPSILON);
+    }
+
+    /// The plain DP, without the shortcuts of `edit_distance`
+    fn edit_distance_slow(a: &str, b: &str, ascii: bool) -> u32 {
+        if ascii {
+            min_distance(a.as_bytes(), b.as_bytes())
+        } else {
+            let a_chars: Vec<char> = a.chars().collect();
+            min_distance(&a_chars, b.chars())
+        }
+    }
+
+    #[test]
+    fn one_edit_shapes() {
+        let pairs = [
+            // Insertions and deletions, at the start, middle, and end
+            ("kitten", "skitten"),
+            ("kitten", "kitsten"),
+            ("kitten", "kittens"),
+            ("maḥmūd", "aḥmūd"),
+            ("maḥmūd", "maḥmd"),
+            // Runs of the same character, where the gap could be anywhere in the run
+            ("committee", "comittee"),
+            // Substitutions
+            ("kitten", "mitten"),
+            ("kitten", "kitsen"),
+            ("kitten", "kittem"),
+            ("شاهنامه", "شاهنامة"),
+        ];
+        for (a, b) in pairs {
+            for ascii in [true, false] {
+                assert_eq!(edit_distance(a, b, ascii), edit_distance_slow(a, b, ascii));
+                assert_eq!(edit_distance(b, a, ascii), edit_distance_slow(a, b, ascii));
+            }
+            assert_eq!(edit_distance(a, b, false), 1);
+        }
+    }
+
+    #[test]
+    fn one_unit_left_after_trimming() {
+        // A single unit against a longer remainder, with or without a match in it
+        assert_eq!(edit_distance("a", "banana", true), 5);
+        assert_eq!(edit_distance("z", "banana", false), 6);
+        assert_eq!(edit_distance("kitchen", "kappen", true), 4);
+        assert_eq!(edit_distance("ʿAī", "ʿUthmān ibn ʿAffānī", false), 16);
+        assert_eq!(edit_distance("ʿAī", "ʿUthmān ibn ʿaffānī", false), 17);
+        assert_eq!(edit_distance_bytes(b"k", b"sitting"), 7);
+    }
+
+    #[test]
+    fn two_edits_fall_through() {
+        for (a, b, dist) in [
+            ("kitten", "sitting", 3),
+            ("kitten", "kittenss", 2),
+            ("kitten", "iktten", 2),
+            ("maḥmūd", "muḥammad", 4),
+        ] {
+            for ascii in [true, false] {
+                assert_eq!(edit_distance(a, b, ascii), edit_distance_slow(a, b, ascii));
+            }
+            assert_eq!(edit_distance(a, b, false), dist);
+        }
+    }
+
+    #[test]
+    fn progress_reported() {
+        let a = "Abū al-Qāsim Firdawsī Ṭūsī ".repeat(40);
+        let b = "Abū Manṣūr Muḥammad ibn ʿAbd al-Razzāq ".repeat(30);
+        for ascii in [true, false] {
+            let mut reports: Vec<f64> = Vec::new();
+            let dist = edit_distance_with_progress(&a, &b, ascii, |f| reports.push(f));
+            assert_eq!(dist, edit_distance(&a, &b, ascii));
+
+            assert!(reports.len() > 50 && reports.len() <= 101);
+            assert!(reports.windows(2).all(|w| w[0] < w[1]));
+            assert!((reports[reports.len() - 1] - 1.0).abs() < f64::EPSILON);
+        }
+
+        // A long shared prefix is worked through like any other rows
+        let prefix = "ʿAlī ibn Abī Ṭālib ".repeat(20);
+        let (a, b) = (format!("{prefix}kitten"), format!("{prefix}sitting"));
+        let mut reports: Vec<f64> = Vec::new();
+        assert_eq!(
+            edit_distance_with_progress(&a, &b, false, |f| reports.push(f)),
+            3
+        );
+        assert!(reports.len() > 50);
+
+        let mut reports: Vec<f64> = Vec::new();
+        assert_eq!(
+            edit_distance_with_progress("", "", true, |f| reports.push(f)),
+            0
+        );
+        assert_eq!(reports, [1.0]);
+    }
+
+    #[test]
+    fn verified_symmetric() {
+        let long = "the quick brown fox jumps over the lazy dog ".repeat(3);
+        let pairs = [
+            ("kitten", "sitting"),
+            ("", "kitten"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("شاهنامه", "شهنامه"),
+            (long.as_str(), "the quick brown dog"),
+            (long.as_str(), "kitten"),
+        ];
+        for (a, b) in pairs {
+            for ascii in [true, false] {
+                assert_eq!(
+                    edit_distance_verified(a, b, ascii),
+                    edit_distance(a, b, ascii)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fraction_kitten_sitting() {
+        assert_eq!(similarity_fraction("kitten", "sitting", true), (4, 7));
+        assert_eq!(similarity_fraction("maḥmūd", "muḥammad", false), (4, 8));
+        assert_eq!(similarity_fraction("maḥmūd", "", false), (0, 6));
+        assert_eq!(similarity_fraction("", "", true), (1, 1));
+
+        let (num, den) = similarity_fraction("شاهنامه", "شهنامه", false);
+        let ratio = similarity_ratio("شاهنامه", "شهنامه", false);
+        assert!((f64::from(num) / f64::from(den) - ratio).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn distance_and_ratio_separately() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("", ""),
+            ("maḥmūd", ""),
+            ("شاهنامه", "شهنامه"),
+        ];
+        for (a, b) in pairs {
+            for ascii in [true, false] {
+                let (dist, ratio) = distance_and_ratio(a, b, ascii);
+                assert_eq!(dist, edit_distance(a, b, ascii));
+                assert!((ratio - similarity_ratio(a, b, ascii)).abs() < f64::EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn lower_bound() {
+        assert_eq!(min_possible_distance("kitten", "sitting", true), 1);
+        assert_eq!(min_possible_distance("sitting", "kitten", false), 1);
+        assert_eq!(min_possible_distance("", "levenshtein", true), 11);
+
+        // "شاهنامه" has 7 `char`s, 14 bytes; "شه" has 2 and 4
+        assert_eq!(min_possible_distance("شاهنامه", "شه", false), 5);
+        assert_eq!(min_possible_distance("شاهنامه", "شه", true), 10);
+        assert!(
+            min_possible_distance("شاهنامه", "شه", false) <= edit_distance("شاهنامه", "شه", false)
+        );
+    }
+
+    #[test]
+    fn chars_iterators() {
+        let pairs = [
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("شاهنامه", "شهنامه"),
+            ("", "maḥmūd"),
+            ("maḥmūd", ""),
+            ("", ""),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(
+                edit_distance_chars(a.chars(), b.chars()),
+                edit_distance(a, b, false)
+            );
+        }
+
+        // Without a useful size hint
+        let decoded = "kitten".chars().filter(|_| true);
+        assert_eq!(edit_distance_chars(decoded, "sitting".chars()), 3);
+    }
+
+    #[test]
+    fn floor_of_last_row() {
+        // "kit" is a prefix of "kitten"
+        assert_eq!(edit_distance_with_floor("kitten", "kit", true), (3, 0));
+        // Closest to "kitten" among the prefixes of "sitting" is "sittin"
+        assert_eq!(edit_distance_with_floor("sitting", "kitten", false), (3, 2));
+        assert_eq!(edit_distance_with_floor("maḥmūd", "", false), (6, 0));
+        assert_eq!(edit_distance_with_floor("", "maḥmūd", false), (6, 6));
+
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿAlī ibn";
+        let floor = (0..=a.chars().count())
+            .map(|j| {
+                let prefix: String = a.chars().take(j).collect();
+                edit_distance(&prefix, b, false)
+            })
+            .min();
+        assert_eq!(
+            edit_distance_with_floor(a, b, false),
+            (edit_distance(a, b, false), floor.unwrap())
+        );
+    }
+
+    #[test]
+    fn utf16_surrogate_pairs() {
+        // "😀" is a surrogate pair; "ā" a single code unit
+        assert_eq!(edit_distance_utf16("smile 😀", "smile"), 3);
+        assert_eq!(edit_distance("smile 😀", "smile", false), 2);
+        assert_eq!(edit_distance_utf16("maḥmūd", "mahmud"), 2);
+        assert_eq!(edit_distance_utf16("😀", "😀"), 0);
+    }
+
+    #[test]
+    fn shared_prefix_skipped() {
+        let prefix = "ʿAlī ibn Abī Ṭālib ".repeat(5);
+        let pairs = [
+            (format!("{prefix}kitten"), format!("{prefix}sitting")),
+            (prefix.clone(), format!("{prefix}x")),
+            (format!("{prefix}x"), prefix.clone()),
+            (format!("{prefix}ab"), format!("{prefix}ba")),
+            ("prefix".to_string(), "pre".to_string()),
+        ];
+
+        // Against the full, unoptimized matrix
+        for (a, b) in &pairs {
+            let a_chars: Vec<char> = a.chars().collect();
+            let b_chars: Vec<char> = b.chars().collect();
+            let matrix = distance_matrix(a, b, false);
+            let expected = matrix[a_chars.len()][b_chars.len()];
+
+            assert_eq!(edit_distance_slice(&a_chars, &b_chars), expected);
+            assert_eq!(min_distance(&a_chars, &b_chars), expected);
+            // The floor is over prefixes of `a`, i.e., the last column here
+            let floor = matrix.iter().map(|row| row[b_chars.len()]).min();
+            assert_eq!(Some(edit_distance_with_floor(a, b, false).1), floor);
+        }
+    }
+
+    #[test]
+    fn auto_shahnama() {
+        let a = "شاهنامه";
+        let b = "شهنامه";
+        assert_eq!(edit_distance_auto(a, b), edit_distance(a, b, false));
+        assert_eq!(edit_distance_auto(a, b), 1);
+    }
+
+    #[test]
+    fn auto_ascii() {
+        assert_eq!(edit_distance_auto("sitting", "kitten"), 3);
+    }
+
+    #[test]
+    fn trimmed_affixes() {
+        let pairs = [
+            ("aaaXaaa", "aaaYaaa"),
+            ("prefix-only", "prefix"),
+            ("suffix", "the-suffix"),
+            ("abcabc", "abc"),
+            ("Ghiyāth al-Dīn", "Ghiyāth al-Dūn"),
+        ];
+
+        for (a, b) in pairs {
+            let a_chars: Vec<char> = a.chars().collect();
+            let b_chars: Vec<char> = b.chars().collect();
+            assert_eq!(edit_distance(a, b, false), min_distance(&a_chars, &b_chars));
+            assert_eq!(
+                edit_distance(a, b, true),
+                min_distance(a.as_bytes(), b.as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn trimmed_long_affixes() {
+        let affix = "ʿAlī ibn Abī Ṭālib ".repeat(20);
+        let a = format!("{affix}kitten{affix}");
+        let b = format!("{affix}sitting{affix}");
+        assert_eq!(edit_distance(&a, &b, false), 3);
+        assert_eq!(edit_distance(&a, &b, true), 3);
+    }
+
+    #[test]
+    fn checked_normal_input() {
+        assert_eq!(edit_distance_checked("sitting", "kitten", true), Some(3));
+        assert_eq!(edit_distance_checked("شاهنامه", "شهنامه", false), Some(1));
+    }
+
+    #[test]
+    fn checked_boundary() {
+        // Six `char`s, but nine bytes
+        let (a, b) = ("maḥmūd", "muḥmad");
+        assert_eq!(checked_with_max(a, b, false, 6), Some(2));
+        assert_eq!(checked_with_max(a, b, true, 8), None);
+        assert_eq!(
+            checked_with_max(a, b, true, 9),
+            Some(edit_distance(a, b, true))
+        );
+        assert_eq!(checked_with_max(a, b, false, 5), None);
+        assert_eq!(checked_with_max(b, a, false, 5), None);
+        assert_eq!(checked_with_max(a, "", false, 5), None);
+
+        assert_eq!(
+            checked_with_max(a, b, true, u32::MAX as usize),
+            edit_distance_checked(a, b, true)
+        );
+    }
+
+    #[test]
+    fn bytes_invalid_utf8() {
+        let a = b"caf\xE9 \xFF\xFE";
+        let b = b"caf\xC3\xA9 \xFF";
+        assert_eq!(edit_distance_bytes(a, b), 3);
+        assert_eq!(edit_distance_bytes(a, a), 0);
+        assert_eq!(edit_distance_bytes(&[], b), 7);
+    }
+
+    #[test]
+    fn bytes_match_ascii_mode() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        assert_eq!(
+            edit_distance_bytes(a.as_bytes(), b.as_bytes()),
+            edit_distance(a, b, true)
+        );
+    }
+
+    #[test]
+    fn streamed_matches_collected() {
+        let pairs = [
+            ("maḥmūd", "muḥammad"),
+            ("شاهنامه", "شهنامه"),
+            ("Ghiyāth al-Dīn", "ʿAlī"),
+            ("ʿAlī", "Ghiyāth al-Dīn"),
+            ("ō", "ōō"),
+        ];
+
+        for (a, b) in pairs {
+            let a_chars: Vec<char> = a.chars().collect();
+            let b_chars: Vec<char> = b.chars().collect();
+            assert_eq!(
+                edit_distance(a, b, false),
+                edit_distance_slice(&a_chars, &b_chars)
+            );
+        }
+    }
+
+    #[test]
+    fn long_first_or_second() {
+        let long = "Abū al-Qāsim Firdawsī Ṭūsī ".repeat(10);
+        let short = "Firdowsi";
+
+        for ascii in [true, false] {
+            assert_eq!(
+                edit_distance(&long, short, ascii),
+                edit_distance(short, &long, ascii)
+            );
+            assert_eq!(
+                edit_distance_within(&long, short, ascii, 300),
+                edit_distance_within(short, &long, ascii, 300)
+            );
+            assert_eq!(
+                banded_distance(&long, short, ascii, 300),
+                banded_distance(short, &long, ascii, 300)
+            );
+        }
+
+        let long_chars: Vec<char> = long.chars().collect();
+        let short_chars: Vec<char> = short.chars().collect();
+        assert_eq!(
+            edit_distance_slice(&long_chars, &short_chars),
+            edit_distance_slice(&short_chars, &long_chars)
+        );
+    }
+
+    #[test]
+    fn within_ratio() {
+        // Ratio 0.571
+        assert_eq!(
+            edit_distance_within_ratio("kitten", "sitting", true, 0.5),
+            Some(3)
+        );
+        // Ratio 0.333
+        assert_eq!(
+            edit_distance_within_ratio("kitten", "bottle", true, 0.5),
+            None
+        );
+        // Exactly at the threshold: 1 - 3/6
+        assert_eq!(
+            edit_distance_within_ratio("kitten", "mutton", true, 0.5),
+            Some(3)
+        );
+
+        // Lengths in `char`s or bytes, depending on the mode
+        assert_eq!(
+            edit_distance_within_ratio("maḥmūd", "mahmud", false, 0.5),
+            Some(2)
+        );
+        assert_eq!(
+            edit_distance_within_ratio("maḥmūd", "mahmud", true, 0.5),
+            None
+        );
+
+        assert_eq!(edit_distance_within_ratio("", "", true, 1.0), Some(0));
+        assert_eq!(edit_distance_within_ratio("abc", "abc", true, 1.5), None);
+        assert_eq!(edit_distance_within_ratio("abc", "xyz", true, 0.0), Some(3));
+    }
+
+    #[test]
+    fn limited() {
+        assert_eq!(edit_distance_limited("kitten", "sitting", true, 7), Ok(3));
+        assert_eq!(
+            edit_distance_limited("kitten", "sitting", true, 6),
+            Err(LengthExceeded { len: 7, max_len: 6 })
+        );
+
+        // Six `char`s, but nine bytes
+        assert_eq!(edit_distance_limited("maḥmūd", "mahmud", false, 6), Ok(2));
+        assert_eq!(
+            edit_distance_limited("maḥmūd", "mahmud", true, 6),
+            Err(LengthExceeded { len: 9, max_len: 6 })
+        );
+        assert_eq!(edit_distance_limited("", "", true, 0), Ok(0));
+    }
+
+    #[test]
+    fn if_closer() {
+        assert_eq!(distance_if_closer("kitten", "sitting", 4, true), Some(3));
+        // Ties and worse candidates are pruned
+        assert_eq!(distance_if_closer("kitten", "sitting", 3, true), None);
+        assert_eq!(distance_if_closer("kitten", "sitting", 2, false), None);
+        assert_eq!(distance_if_closer("kitten", "kitten", 0, true), None);
+        assert_eq!(distance_if_closer("kitten", "kitten", 1, false), Some(0));
+    }
+
+    #[test]
+    fn compact_rows_match_wide() {
+        let words = [
+            "ʿAlī ibn Abī Ṭālib",
+            "ʿUthmān ibn ʿAffān",
+            "maḥmūd",
+            "",
+            "شاهنامه",
+        ];
+        for a in words {
+            for b in words {
+                let a_chars: Vec<char> = a.chars().collect();
+                let b_chars: Vec<char> = b.chars().collect();
+
+                let mut dp_prev: Vec<u16> = Vec::new();
+                let mut dp_curr: Vec<u16> = Vec::new();
+                assert_eq!(
+                    min_distance_buffered(&a_chars, &b_chars, &mut dp_prev, &mut dp_curr),
+                    min_distance(&a_chars, &b_chars)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compact_rows_threshold() {
+        assert!(fits_u16(65_534, 65_534));
+        assert!(!fits_u16(3, 65_535));
+
+        // Too long for `u16` cells, so they mustn't be used
+        let long = "ab".repeat(40_000);
+        assert_eq!(edit_distance("abc", &long, false), 79_998);
+        assert_eq!(edit_distance_slice(b"abc", long.as_bytes()), 79_998);
+    }
+
+    #[test]
+    fn within_k() {
+        assert!(is_within("sitting", "kitten", 3, true));
+        assert!(!is_within("sitting", "kitten", 2, true));
+        assert!(!is_within("sitting", "kitten", 2, false));
+        // Rejected on length alone
+        assert!(!is_within("ʿAlī ibn Abī Ṭālib", "ʿAlī", 10, false));
+        assert!(is_within("", "", 0, false));
+    }
+
+    #[test]
+    fn ascii_buffers_reused() {
+        let long = "the quick brown fox jumps over the lazy dog ".repeat(3);
+        let (long_o, long_e) = (long.replace('o', "0"), long.replace('e', "3"));
+        let pairs = [
+            ("kitten", "sitting"),
+            (long.as_str(), "the quick brown fox"),
+            ("", "abc"),
+            (long.as_str(), long.as_str()),
+            (long_o.as_str(), long_e.as_str()),
+        ];
+
+        let mut prev: Vec<u32> = Vec::new();
+        let mut curr: Vec<u32> = vec![7; 3];
+        for (a, b) in pairs {
+            let dist = edit_distance_ascii_buf(a.as_bytes(), b.as_bytes(), &mut prev, &mut curr);
+            assert_eq!(dist, edit_distance(a, b, true));
+        }
+
+        // Once large enough, the buffers are reused as they are
+        let capacity = prev.capacity();
+        assert!(capacity > 64);
+        let (a, b) = (long.replace('u', "U"), long.replace('h', "H"));
+        assert_eq!(
+            edit_distance_ascii_buf(a.as_bytes(), b.as_bytes(), &mut prev, &mut curr),
+            edit_distance(&a, &b, true)
+        );
+        assert_eq!(prev.capacity(), capacity);
+    }
 }
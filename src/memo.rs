@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::edit_distance;
+
+/// A cache of Levenshtein distances, for pipelines that compare the same pairs of strings
+/// repeatedly
+///
+/// ```
+/// use agnostic_levenshtein::MemoizedDistance;
+///
+/// let mut memo = MemoizedDistance::new();
+/// assert_eq!(memo.distance("kitten", "sitting", true), 3);
+/// assert_eq!(memo.distance("sitting", "kitten", true), 3);
+/// assert_eq!(memo.len(), 1);
+/// ```
+///
+/// Since distance is symmetric, each pair is stored only once, whichever order it comes
+/// in. Entries for ASCII and Unicode mode are kept apart. Requires the `std` feature.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoizedDistance {
+    // For each mode, the lesser string of each pair, then the greater; nesting the maps
+    // lets them be probed with borrowed keys
+    ascii: Pairs,
+    unicode: Pairs,
+}
+
+type Pairs = HashMap<String, HashMap<String, u32>>;
+
+impl MemoizedDistance {
+    /// Creates an empty cache
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the Levenshtein distance between `a` and `b`, computing it only if it
+    /// isn't already cached. The `ascii` flag works as for `edit_distance`.
+    ///
+    /// A hit doesn't allocate; a miss copies whichever of the strings isn't yet a key.
+    pub fn distance(&mut self, a: &str, b: &str, ascii: bool) -> u32 {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        let pairs = if ascii {
+            &mut self.ascii
+        } else {
+            &mut self.unicode
+        };
+
+        if let Some(&dist) = pairs.get(a).and_then(|row| row.get(b)) {
+            return dist;
+        }
+
+        let dist = edit_distance(a, b, ascii);
+        if let Some(row) = pairs.get_mut(a) {
+            row.insert(b.to_string(), dist);
+        } else {
+            pairs.insert(a.to_string(), HashMap::from([(b.to_string(), dist)]));
+        }
+        dist
+    }
+
+    /// Returns the number of cached pairs
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ascii
+            .values()
+            .chain(self.unicode.values())
+            .map(HashMap::len)
+            .sum()
+    }
+
+    /// Whether the cache is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ascii.is_empty() && self.unicode.is_empty()
+    }
+
+    /// Removes all cached pairs
+    pub fn clear(&mut self) {
+        self.ascii.clear();
+        self.unicode.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_call_cached() {
+        let mut memo = MemoizedDistance::new();
+        assert!(memo.is_empty());
+
+        assert_eq!(memo.distance("maḥmūd", "muḥammad", false), 4);
+        assert_eq!(memo.len(), 1);
+
+        // Pretend the entry was computed differently, to see that it's reused
+        let row = memo.unicode.values_mut().next().unwrap();
+        *row.values_mut().next().unwrap() = 99;
+        assert_eq!(memo.distance("maḥmūd", "muḥammad", false), 99);
+        assert_eq!(memo.distance("muḥammad", "maḥmūd", false), 99);
+        assert_eq!(memo.len(), 1);
+    }
+
+    #[test]
+    fn modes_kept_apart() {
+        let mut memo = MemoizedDistance::new();
+        assert_eq!(memo.distance("شاهنامه", "شهنامه", false), 1);
+        assert_eq!(memo.distance("شاهنامه", "شهنامه", true), 2);
+        assert_eq!(memo.len(), 2);
+
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn pairs_share_keys() {
+        let mut memo = MemoizedDistance::new();
+        assert_eq!(memo.distance("kitten", "sitting", true), 3);
+        assert_eq!(memo.distance("mitten", "kitten", true), 1);
+        assert_eq!(memo.distance("kitten", "kitten", true), 0);
+        assert_eq!(memo.len(), 3);
+        // All three pairs are stored under their lesser string, "kitten"
+        assert_eq!(memo.ascii.len(), 1);
+        assert_eq!(memo.ascii["kitten"].len(), 3);
+        assert_eq!(memo.ascii["kitten"]["sitting"], 3);
+    }
+}
@@ -0,0 +1,103 @@
+/// Returns the Levenshtein distance between `a` and `b` using Myers' bit-parallel
+/// algorithm. The shorter of the two must fit in a single 64-bit word.
+pub fn myers_distance(a: &[u8], b: &[u8]) -> u32 {
+    let (pattern, text) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    MyersPattern::new(pattern).distance(text)
+}
+
+/// A pattern of at most 64 bytes, preprocessed for Myers' algorithm (in Hyyrö's
+/// formulation for edit distance), which handles a whole column of the DP matrix per
+/// machine-word operation
+pub struct MyersPattern {
+    // Bitmask of the positions in the pattern at which each byte occurs
+    peq: [u64; 256],
+    len: usize,
+}
+
+impl MyersPattern {
+    pub fn new(pattern: &[u8]) -> Self {
+        debug_assert!(pattern.len() <= 64);
+
+        let mut peq = [0_u64; 256];
+        for (i, &c) in pattern.iter().enumerate() {
+            peq[c as usize] |= 1 << i;
+        }
+
+        Self {
+            peq,
+            len: pattern.len(),
+        }
+    }
+
+    /// Returns the distance between the pattern and `text`, which may be of any length
+    pub fn distance(&self, text: &[u8]) -> u32 {
+        if self.len == 0 {
+            return text.len() as u32;
+        }
+
+        // Vertical deltas (positive and negative) of the current column
+        let mut pv: u64 = !0;
+        let mut mv: u64 = 0;
+        let mut score = self.len as u32;
+        let last = 1 << (self.len - 1);
+
+        for &c in text {
+            let eq = self.peq[c as usize];
+            let xv = eq | mv;
+            let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+
+            // Horizontal deltas
+            let mut ph = mv | !(xh | pv);
+            let mut mh = pv & xh;
+
+            if ph & last != 0 {
+                score += 1;
+            } else if mh & last != 0 {
+                score -= 1;
+            }
+
+            // The top row increases by one with each step through the text
+            ph = (ph << 1) | 1;
+            mh <<= 1;
+
+            pv = mh | !(xv | ph);
+            mv = ph & xv;
+        }
+
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::min_distance;
+    use crate::test_util::Rng;
+
+    #[test]
+    fn sitting_kitten() {
+        assert_eq!(myers_distance(b"sitting", b"kitten"), 3);
+        assert_eq!(myers_distance(b"kitten", b"sitting"), 3);
+    }
+
+    #[test]
+    fn random_against_dp() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+        for alphabet in [&b"ACGT"[..], b"ab", b"abcdefghijklmnopqrstuvwxyz"] {
+            for _ in 0..2000 {
+                let a = rng.bytes(64, alphabet);
+                let b = rng.bytes(100, alphabet);
+                assert_eq!(myers_distance(&a, &b), min_distance(&a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn full_word() {
+        let a = [b'x'; 64];
+        let b = [b'y'; 64];
+        assert_eq!(myers_distance(&a, &b), 64);
+        assert_eq!(myers_distance(&a, &a[..1]), 63);
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::vec::Vec;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::canonical_combining_class;
+
+use crate::prep::{NormalizeOpts, normalize};
+use crate::{edit_distance, edit_distance_slice};
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, after
+/// applying Unicode NFC normalization to both.
+///
+/// This ensures that canonically equivalent strings—e.g., "é" as a single code point
+/// and as "e" plus a combining acute accent—are compared alike. The normalized strings
+/// are always compared as sequences of `char`s. Requires the `normalization` feature.
+#[must_use]
+pub fn edit_distance_normalized(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let opts = NormalizeOpts {
+        nfc: true,
+        ..NormalizeOpts::default()
+    };
+    edit_distance(&normalize(a, opts), &normalize(b, opts), false)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, ignoring
+/// diacritics.
+///
+/// Both strings are decomposed (NFD), and their combining marks (those with a nonzero
+/// canonical combining class) removed, so that "café" and "cafe", or "Ṭālib" and
+/// "Talib", are compared alike. Decomposition and classification both come from the
+/// `unicode-normalization` crate, so they follow the same version of Unicode. Letters with no decomposition, such as "ø" or "ʿ", are kept as they
+/// are, so the result need not be ASCII. The stripped strings are compared as sequences
+/// of `char`s. Requires the `normalization` feature, which provides the Unicode data.
+#[must_use]
+pub fn edit_distance_ascii_fold(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let strip = |s: &str| -> Vec<char> {
+        nfd(s)
+            .into_iter()
+            .filter(|&c| canonical_combining_class(c) == 0)
+            .collect()
+    };
+    edit_distance_slice(&strip(a), &strip(b))
+}
+
+/// Returns the NFD normalization of `s` as a sequence of `char`s
+fn nfd(s: &str) -> Vec<char> {
+    s.nfd().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precomposed_decomposed() {
+        let a = "\u{E9}";
+        let b = "e\u{301}";
+        assert_eq!(crate::edit_distance(a, b, false), 2);
+        assert_eq!(edit_distance_normalized(a, b), 0);
+    }
+
+    #[test]
+    fn mark_order() {
+        // Dot below (class 220) and dot above (class 230) in either order
+        let a = "q\u{307}\u{323}";
+        let b = "q\u{323}\u{307}";
+        assert_eq!(edit_distance_normalized(a, b), 0);
+        assert_eq!(
+            edit_distance_normalized("\u{1E0B}\u{323}", "d\u{323}\u{307}"),
+            0
+        );
+    }
+
+    #[test]
+    fn hangul() {
+        let a = "\u{D55C}\u{AE00}";
+        let b = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}";
+        assert_eq!(edit_distance_normalized(a, b), 0);
+    }
+
+    #[test]
+    fn normalized_distance() {
+        assert_eq!(edit_distance_normalized("Ṭālib", "Ta\u{304}lib"), 1);
+        assert_eq!(edit_distance_normalized("sitting", "kitten"), 3);
+    }
+
+    #[test]
+    fn diacritics_ignored() {
+        assert_eq!(edit_distance_ascii_fold("cafe", "café"), 0);
+        assert_eq!(edit_distance_ascii_fold("cafe", "cafe\u{301}"), 0);
+        assert_eq!(
+            edit_distance_ascii_fold("ʿAlī ibn Abī Ṭālib", "Ali ibn Abi Talib"),
+            1
+        );
+        // No decomposition, so not folded
+        assert_eq!(edit_distance_ascii_fold("Søren", "Soren"), 1);
+        assert_eq!(edit_distance_ascii_fold("sitting", "kitten"), 3);
+        // Arabic pepet, a mark added in Unicode 16.0
+        assert_eq!(edit_distance_ascii_fold("\u{628}\u{897}", "\u{628}"), 0);
+    }
+}
@@ -0,0 +1,61 @@
+use std::ffi::OsStr;
+
+use crate::edit_distance_bytes;
+
+/// Returns the Levenshtein distance (`u32`) between two OS strings, `a` and `b`, such as
+/// file names or path components, which need not be valid UTF-8
+///
+/// The strings are compared byte by byte, via `OsStr::as_encoded_bytes`, as in
+/// `edit_distance_bytes`: so a multibyte character counts as several units, even where
+/// the whole string is valid UTF-8. On Unix and WASI, the bytes are those of the string
+/// itself, in whatever encoding it has. On Windows, where OS strings are potentially
+/// ill-formed UTF-16, they are an internal encoding that is a superset of UTF-8 (the
+/// same bytes, for valid Unicode), so unpaired surrogates are compared like any other
+/// code point. Requires the `std` feature.
+///
+/// To count `char`s instead, convert with `OsStr::to_str` (or `to_string_lossy`) and use
+/// `edit_distance`.
+#[must_use]
+pub fn edit_distance_os(a: &OsStr, b: &OsStr) -> u32 {
+    edit_distance_bytes(a.as_encoded_bytes(), b.as_encoded_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            edit_distance_os(OsStr::new("src/lib.rs"), OsStr::new("src/lob.rs")),
+            1
+        );
+        assert_eq!(
+            edit_distance_os(
+                Path::new("/usr/local/bin").as_os_str(),
+                Path::new("/usr/bin").as_os_str()
+            ),
+            6
+        );
+        // Bytes, not `char`s
+        assert_eq!(
+            edit_distance_os(OsStr::new("shāhnāma.txt"), OsStr::new("shahnama.txt")),
+            4
+        );
+        assert_eq!(edit_distance_os(OsStr::new(""), OsStr::new("")), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let a = OsStr::from_bytes(b"caf\xE9.txt");
+        let b = OsStr::from_bytes(b"caf\xC3\xA9.txt");
+        assert!(a.to_str().is_none());
+        assert_eq!(edit_distance_os(a, b), 2);
+        assert_eq!(edit_distance_os(a, OsStr::new("cafe.txt")), 1);
+    }
+}
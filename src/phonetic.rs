@@ -0,0 +1,110 @@
+use alloc::string::String;
+
+use crate::edit_distance;
+
+/// Returns the American Soundex code of `s`: its first letter followed by three digits,
+/// e.g., "R163" for both "Robert" and "Rupert"
+///
+/// The digits encode the consonants after the first letter, with similar-sounding ones
+/// sharing a digit; vowels are dropped, and adjacent consonants with the same digit
+/// count once (as do those separated only by "h" or "w"). Codes are padded with zeros
+/// to four characters. Only ASCII letters are considered, so the result is empty if `s`
+/// has none.
+#[must_use]
+pub fn soundex(s: &str) -> String {
+    let mut letters = s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase());
+
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    let mut last = digit(first);
+
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+
+        match c {
+            // Transparent: consonants on either side with the same digit are merged
+            'H' | 'W' => {}
+            _ => {
+                let d = digit(c);
+                if let Some(d) = d
+                    && last != Some(d)
+                {
+                    code.push(d);
+                }
+                last = d;
+            }
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+
+    code
+}
+
+/// Returns the Levenshtein distance between the Soundex codes of two strings, `a` and
+/// `b`, as computed by `soundex`.
+///
+/// This is 0 for names that sound alike, however they're spelled (e.g., "Smith" and
+/// "Smyth"), and at most 4 otherwise. It suits names in English, not general text.
+#[must_use]
+pub fn phonetic_distance(a: &str, b: &str) -> u32 {
+    edit_distance(&soundex(a), &soundex(b), true)
+}
+
+/// The Soundex digit of an uppercase letter, or `None` for vowels (and "y", "h", "w")
+const fn digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn robert_rupert() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(phonetic_distance("Robert", "Rupert"), 0);
+        assert_eq!(phonetic_distance("Smith", "Smyth"), 0);
+    }
+
+    #[test]
+    fn reference_codes() {
+        assert_eq!(soundex("Rubin"), "R150");
+        assert_eq!(soundex("Tymczak"), "T522");
+        // Same digit as the first letter
+        assert_eq!(soundex("Pfister"), "P236");
+        // "s" and "c" separated by "h"
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Honeyman"), "H555");
+        assert_eq!(soundex("lee"), "L000");
+    }
+
+    #[test]
+    fn no_letters() {
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("ʿ123"), "");
+        assert_eq!(soundex("ʿAlī"), "A400");
+        assert_eq!(phonetic_distance("Robert", "Rubin"), 2);
+        assert_eq!(phonetic_distance("", "Lee"), 4);
+    }
+}
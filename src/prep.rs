@@ -0,0 +1,156 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+#[cfg(feature = "normalization")]
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+/// Transformations to apply to a string before comparing it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct NormalizeOpts {
+    /// Whether the string can be treated as ASCII-only, as for `edit_distance`; this
+    /// affects only lowercasing
+    pub ascii: bool,
+    /// Whether to strip leading and trailing whitespace
+    pub trim: bool,
+    /// Whether to replace each run of whitespace with a single space
+    pub collapse_whitespace: bool,
+    /// Whether to apply Unicode NFC normalization
+    #[cfg(feature = "normalization")]
+    pub nfc: bool,
+    /// Whether to lowercase letters: ASCII letters only in ASCII mode, otherwise every
+    /// `char` (possibly expanding some of them)
+    pub lowercase: bool,
+}
+
+/// Returns `s` transformed according to `opts`, allocating only if anything changes
+pub fn normalize(s: &str, opts: NormalizeOpts) -> Cow<'_, str> {
+    let mut out = Cow::Borrowed(if opts.trim { s.trim() } else { s });
+
+    if opts.collapse_whitespace && needs_collapse(&out) {
+        let mut collapsed = String::with_capacity(out.len());
+        let mut in_run = false;
+        for c in out.chars() {
+            if !c.is_whitespace() {
+                collapsed.push(c);
+            } else if !in_run {
+                collapsed.push(' ');
+            }
+            in_run = c.is_whitespace();
+        }
+        out = Cow::Owned(collapsed);
+    }
+
+    // ASCII-only text is always in NFC
+    #[cfg(feature = "normalization")]
+    if opts.nfc && !out.is_ascii() && is_nfc_quick(out.chars()) != IsNormalized::Yes {
+        let composed: String = out.nfc().collect();
+        if composed != *out {
+            out = Cow::Owned(composed);
+        }
+    }
+
+    if opts.lowercase {
+        if opts.ascii {
+            if out.bytes().any(|c| c.is_ascii_uppercase()) {
+                out = Cow::Owned(out.to_ascii_lowercase());
+            }
+        } else if out.chars().any(|c| !is_lowercase_fixed(c)) {
+            let lower: String = out.chars().flat_map(char::to_lowercase).collect();
+            out = Cow::Owned(lower);
+        }
+    }
+
+    out
+}
+
+/// Whether `s` has any whitespace other than single spaces
+fn needs_collapse(s: &str) -> bool {
+    let mut prev_space = false;
+    s.chars().any(|c| {
+        let collapsible = c.is_whitespace() && (c != ' ' || prev_space);
+        prev_space = c == ' ';
+        collapsible
+    })
+}
+
+/// Whether `c` is unchanged by `char::to_lowercase`
+fn is_lowercase_fixed(c: char) -> bool {
+    let mut lower = c.to_lowercase();
+    lower.next() == Some(c) && lower.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lowercase(ascii: bool) -> NormalizeOpts {
+        NormalizeOpts {
+            ascii,
+            lowercase: true,
+            ..NormalizeOpts::default()
+        }
+    }
+
+    #[test]
+    fn borrowed_if_unchanged() {
+        assert!(matches!(
+            normalize("kitten", lowercase(true)),
+            Cow::Borrowed("kitten")
+        ));
+        assert!(matches!(
+            normalize("ʿalī", lowercase(false)),
+            Cow::Borrowed("ʿalī")
+        ));
+        // Non-ASCII letters are left alone in ASCII mode
+        assert!(matches!(normalize("ĪSĀ", lowercase(true)), Cow::Owned(_)));
+        assert!(matches!(
+            normalize("Ī", lowercase(true)),
+            Cow::Borrowed("Ī")
+        ));
+
+        let trim = NormalizeOpts {
+            trim: true,
+            ..NormalizeOpts::default()
+        };
+        assert!(matches!(
+            normalize("  kitten\n", trim),
+            Cow::Borrowed("kitten")
+        ));
+    }
+
+    #[test]
+    fn owned_if_changed() {
+        let lower = normalize("ʿALĪ", lowercase(false));
+        assert!(matches!(lower, Cow::Owned(_)));
+        assert_eq!(lower, "ʿalī");
+
+        assert_eq!(normalize("McDonald", lowercase(true)), "mcdonald");
+        assert_eq!(normalize("İ", lowercase(false)), "i\u{307}");
+    }
+
+    #[test]
+    fn collapsed_whitespace() {
+        let opts = NormalizeOpts {
+            collapse_whitespace: true,
+            ..NormalizeOpts::default()
+        };
+        assert!(matches!(normalize(" a b ", opts), Cow::Borrowed(" a b ")));
+        assert_eq!(normalize("a  \t b\n", opts), "a b ");
+        assert_eq!(normalize("a\u{A0}b", opts), "a b");
+    }
+
+    #[test]
+    #[cfg(feature = "normalization")]
+    fn nfc_only_if_needed() {
+        let opts = NormalizeOpts {
+            nfc: true,
+            ..NormalizeOpts::default()
+        };
+        assert!(matches!(normalize("é", opts), Cow::Borrowed("é")));
+
+        let composed = normalize("e\u{301}", opts);
+        assert!(matches!(composed, Cow::Owned(_)));
+        assert_eq!(composed, "é");
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns the edit distance (`u32`) between two strings, `a` and `b`, where `rules` let
+/// spans of one be replaced with spans of the other at given costs
+///
+/// ```
+/// use agnostic_levenshtein::edit_distance_with_rules;
+///
+/// assert_eq!(edit_distance_with_rules("khan", "xan", &[("kh", "x", 1)]), 1);
+/// assert_eq!(edit_distance_with_rules("khan", "xan", &[]), 2);
+/// ```
+///
+/// A rule `(x, y, cost)` applies wherever `x` occurs in `a` and `y` at the corresponding
+/// point in `b`, or the other way around, and consumes both spans. Single-character
+/// insertions, deletions, and substitutions remain available at a cost of 1, so a rule
+/// only ever lowers the distance. Rules with two empty spans are ignored. Strings are
+/// compared as sequences of `char`s.
+///
+/// Since a rule can reach back several rows, this keeps the whole DP table in memory,
+/// for O(m × n) space, and checks every rule at every cell.
+#[must_use]
+pub fn edit_distance_with_rules(a: &str, b: &str, rules: &[(&str, &str, u32)]) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // Each rule in both orientations, as (span of `a`, span of `b`, cost)
+    let mut spans: Vec<(Vec<char>, Vec<char>, u32)> = Vec::with_capacity(rules.len() * 2);
+    for &(x, y, cost) in rules {
+        if x.is_empty() && y.is_empty() {
+            continue;
+        }
+        let x: Vec<char> = x.chars().collect();
+        let y: Vec<char> = y.chars().collect();
+        if x != y {
+            spans.push((y.clone(), x.clone(), cost));
+        }
+        spans.push((x, y, cost));
+    }
+
+    let (m, n) = (a.len(), b.len());
+    let width = n + 1;
+    let mut dp: Vec<u32> = vec![0; (m + 1) * width];
+
+    for i in 0..=m {
+        for j in 0..=n {
+            let mut best = if i == 0 {
+                j as u32
+            } else if j == 0 {
+                i as u32
+            } else {
+                let sub_cost = u32::from(a[i - 1] != b[j - 1]);
+                let delete = dp[(i - 1) * width + j] + 1;
+                let insert = dp[i * width + j - 1] + 1;
+                let substitute = dp[(i - 1) * width + j - 1] + sub_cost;
+                delete.min(insert).min(substitute)
+            };
+
+            for (x, y, cost) in &spans {
+                if a[..i].ends_with(x) && b[..j].ends_with(y) {
+                    let from = dp[(i - x.len()) * width + j - y.len()];
+                    best = best.min(from.saturating_add(*cost));
+                }
+            }
+
+            dp[i * width + j] = best;
+        }
+    }
+
+    dp[m * width + n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn span_rules() {
+        let rules = [("kh", "x", 1), ("sh", "ش", 0), ("ā", "aa", 0)];
+        assert_eq!(edit_distance_with_rules("khan", "xan", &rules), 1);
+        // Either way around
+        assert_eq!(edit_distance_with_rules("xan", "khan", &rules), 1);
+        assert_eq!(edit_distance_with_rules("shāh", "شaah", &rules), 0);
+        assert_eq!(edit_distance_with_rules("khāsh", "xaaش", &rules), 1);
+        // A rule never makes things worse
+        assert_eq!(edit_distance_with_rules("kh", "k", &[("kh", "k", 5)]), 1);
+    }
+
+    #[test]
+    fn empty_spans() {
+        // Dropping an "h" for free
+        let rules = [("h", "", 0), ("", "", 0)];
+        assert_eq!(edit_distance_with_rules("shah", "sa", &rules), 0);
+        assert_eq!(edit_distance_with_rules("sa", "shah", &rules), 0);
+    }
+
+    #[test]
+    fn no_rules() {
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("", "شاهنامه"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+        ] {
+            assert_eq!(
+                edit_distance_with_rules(a, b, &[]),
+                edit_distance(a, b, false)
+            );
+        }
+    }
+}
@@ -0,0 +1,965 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ParseError, ScriptMismatch};
+
+/// A single step in an edit script, as returned by `edit_script`
+///
+/// With the `serde` feature, this is serializable, with each `char` as a one-character
+/// string; e.g., in JSON, `EditOp::Substitute { from: 'k', to: 's' }` is
+/// `{"Substitute":{"from":"k","to":"s"}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EditOp {
+    /// Insert a character from `b`
+    Insert(char),
+    /// Delete a character from `a`
+    Delete(char),
+    /// Replace a character from `a` with one from `b`
+    Substitute {
+        /// The character in `a`
+        from: char,
+        /// The character in `b`
+        to: char,
+    },
+    /// Keep a character common to both strings
+    Match(char),
+}
+
+/// Which operation `edit_script_with` prefers where several minimal edit scripts diverge
+///
+/// The script is built back to front, and at each step a match is taken if possible;
+/// otherwise, the preferred operation, if it lies on a minimal path, and then the others
+/// in the order substitution, deletion, insertion. So each policy gives a different
+/// script, but always one of the same length.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[allow(clippy::enum_variant_names)]
+pub enum TieBreak {
+    /// Prefer substituting a character to deleting one and inserting another
+    #[default]
+    PreferSubstitution,
+    /// Prefer inserting characters from `b`
+    PreferInsertion,
+    /// Prefer deleting characters from `a`
+    PreferDeletion,
+}
+
+/// An edit script together with the distance it represents, i.e., the number of
+/// operations other than `EditOp::Match`
+///
+/// With the `serde` feature, this is serializable, like `EditOp`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceResult {
+    /// The Levenshtein distance
+    pub distance: u32,
+    /// The edit operations, as returned by `edit_script`
+    pub ops: Vec<EditOp>,
+}
+
+impl From<Vec<EditOp>> for DistanceResult {
+    fn from(ops: Vec<EditOp>) -> Self {
+        let distance = ops
+            .iter()
+            .filter(|op| !matches!(op, EditOp::Match(_)))
+            .count() as u32;
+        Self { distance, ops }
+    }
+}
+
+/// Counts of each kind of edit operation making up a Levenshtein distance, as returned by
+/// `edit_distance_breakdown`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Breakdown {
+    /// Characters inserted from `b`
+    pub insertions: u32,
+    /// Characters deleted from `a`
+    pub deletions: u32,
+    /// Characters of `a` replaced with ones from `b`
+    pub substitutions: u32,
+    /// The Levenshtein distance, i.e., the sum of the other counts
+    pub total: u32,
+}
+
+impl From<&[EditOp]> for Breakdown {
+    fn from(ops: &[EditOp]) -> Self {
+        let mut breakdown = Self::default();
+
+        for op in ops {
+            match op {
+                EditOp::Insert(_) => breakdown.insertions += 1,
+                EditOp::Delete(_) => breakdown.deletions += 1,
+                EditOp::Substitute { .. } => breakdown.substitutions += 1,
+                EditOp::Match(_) => continue,
+            }
+            breakdown.total += 1;
+        }
+
+        breakdown
+    }
+}
+
+/// Returns the numbers of insertions, deletions, and substitutions in a minimal edit
+/// script transforming `a` into `b`. The `ascii` flag works as for `edit_script`.
+///
+/// There may be several minimal scripts with different mixes of operations (though the
+/// same total). This counts the one given by `edit_script`, which, working back from the
+/// ends of the strings, prefers a match, then a substitution, then a deletion, and only
+/// then an insertion.
+#[must_use]
+pub fn edit_distance_breakdown(a: &str, b: &str, ascii: bool) -> Breakdown {
+    Breakdown::from(edit_script(a, b, ascii).as_slice())
+}
+
+/// Returns a minimal sequence of edit operations transforming `a` into `b`. The `ascii`
+/// flag indicates whether the strings can be treated as ASCII-only.
+///
+/// The operations are in left-to-right order, so replaying them against `a` yields `b`.
+/// The number of operations other than `EditOp::Match` equals `edit_distance(a, b, ascii)`.
+/// In ASCII mode, each byte is reported as the `char` with the same value.
+///
+/// Where several scripts are equally short, this returns the one preferred by
+/// `TieBreak::PreferSubstitution`; use `edit_script_with` to choose otherwise.
+#[must_use]
+pub fn edit_script(a: &str, b: &str, ascii: bool) -> Vec<EditOp> {
+    edit_script_with(a, b, ascii, TieBreak::default())
+}
+
+/// Returns a minimal sequence of edit operations transforming `a` into `b`, choosing
+/// among equally short ones according to `tie_break`. The `ascii` flag works as for
+/// `edit_script`.
+///
+/// ```
+/// use agnostic_levenshtein::{EditOp, TieBreak, edit_script_with};
+///
+/// let script = edit_script_with("ab", "ba", true, TieBreak::PreferDeletion);
+/// assert_eq!(
+///     script,
+///     [EditOp::Insert('b'), EditOp::Match('a'), EditOp::Delete('b')]
+/// );
+/// ```
+#[must_use]
+pub fn edit_script_with(a: &str, b: &str, ascii: bool, tie_break: TieBreak) -> Vec<EditOp> {
+    if ascii {
+        backtrack(a.as_bytes(), b.as_bytes(), tie_break)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        backtrack(&a_chars, &b_chars, tie_break)
+    }
+}
+
+/// Returns the operations of `edit_script(a, b, ascii)` other than matches, each with
+/// the position at which it applies, e.g., to highlight the changed characters
+///
+/// ```
+/// use agnostic_levenshtein::{EditOp, edit_positions};
+///
+/// assert_eq!(
+///     edit_positions("sitting", "kitten", true),
+///     [
+///         (0, EditOp::Substitute { from: 's', to: 'k' }),
+///         (4, EditOp::Substitute { from: 'i', to: 'e' }),
+///         (6, EditOp::Delete('g')),
+///     ]
+/// );
+/// ```
+///
+/// Deletions and substitutions are positioned by their index in `a`, and insertions by
+/// their index in `b`. Indexes count bytes in ASCII mode and `char`s otherwise (the same
+/// thing, for ASCII-only strings).
+#[must_use]
+pub fn edit_positions(a: &str, b: &str, ascii: bool) -> Vec<(usize, EditOp)> {
+    let (mut i, mut j) = (0, 0);
+    let mut positions: Vec<(usize, EditOp)> = Vec::new();
+
+    for op in edit_script(a, b, ascii) {
+        match op {
+            EditOp::Insert(_) => {
+                positions.push((j, op));
+                j += 1;
+            }
+            EditOp::Delete(_) => {
+                positions.push((i, op));
+                i += 1;
+            }
+            EditOp::Substitute { .. } => {
+                positions.push((i, op));
+                i += 1;
+                j += 1;
+            }
+            EditOp::Match(_) => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    positions
+}
+
+/// Returns, for each unit of `a`, the index of the unit of `b` it is aligned with in
+/// `edit_script(a, b, ascii)`, or `None` if it is deleted
+///
+/// ```
+/// use agnostic_levenshtein::index_map;
+///
+/// assert_eq!(index_map("cat", "cart", true), [Some(0), Some(1), Some(3)]);
+/// ```
+///
+/// A matched or substituted unit maps to its counterpart, so the mapping can carry
+/// annotations (e.g., highlights or footnote anchors) over from one version of a text to
+/// another. Indexes count bytes in ASCII mode and `char`s otherwise, in both strings.
+/// Since alignments preserve order, the `Some` entries are strictly increasing; units of
+/// `b` that appear in none of them were inserted.
+#[must_use]
+pub fn index_map(a: &str, b: &str, ascii: bool) -> Vec<Option<usize>> {
+    let mut map: Vec<Option<usize>> = Vec::with_capacity(a.len());
+    let mut j = 0;
+
+    for op in edit_script(a, b, ascii) {
+        match op {
+            EditOp::Insert(_) => j += 1,
+            EditOp::Delete(_) => map.push(None),
+            EditOp::Substitute { .. } | EditOp::Match(_) => {
+                map.push(Some(j));
+                j += 1;
+            }
+        }
+    }
+
+    map
+}
+
+/// Returns the result of replaying the edit operations in `script` against `a`, e.g., to
+/// recover `b` from `a` and `edit_script(a, b, ascii)`.
+///
+/// Every operation other than `EditOp::Insert` consumes the next `char` of `a`, which
+/// must be the one the operation names, and the script must consume all of `a`. Scripts
+/// from the ASCII mode of `edit_script` name bytes as `char`s, so they only fit
+/// ASCII-only strings.
+///
+/// # Errors
+///
+/// Returns `ScriptMismatch` if the script doesn't fit `a`.
+pub fn apply_script(a: &str, script: &[EditOp]) -> Result<String, ScriptMismatch> {
+    let mut a_chars = a.chars();
+    let mut out = String::with_capacity(a.len());
+
+    for (index, &op) in script.iter().enumerate() {
+        let consumed = match op {
+            EditOp::Insert(c) => {
+                out.push(c);
+                continue;
+            }
+            EditOp::Delete(c) => c,
+            EditOp::Substitute { from, to } => {
+                out.push(to);
+                from
+            }
+            EditOp::Match(c) => {
+                out.push(c);
+                c
+            }
+        };
+
+        if a_chars.next() != Some(consumed) {
+            return Err(ScriptMismatch { index });
+        }
+    }
+
+    if a_chars.next().is_some() {
+        return Err(ScriptMismatch {
+            index: script.len(),
+        });
+    }
+
+    Ok(out)
+}
+
+/// Returns a compact, human-readable encoding of `script`, which `decode_diff` turns
+/// back into the same script, given the string it applies to
+///
+/// ```
+/// use agnostic_levenshtein::{decode_diff, edit_script, encode_diff};
+///
+/// let script = edit_script("sitting", "kitten", true);
+/// let diff = encode_diff(&script);
+/// assert_eq!(diff, "~sk =3 ~ie =1 -g");
+/// assert_eq!(decode_diff("sitting", &diff), Ok(script));
+/// ```
+///
+/// The encoding is a space-separated list of tokens, each of a sigil and what it applies
+/// to: `=` and the length of a run of matches, `-` and the characters of a run of
+/// deletions, `+` and those of a run of insertions, and `~` and a single substitution,
+/// as the character replaced and the one replacing it. Within tokens other than matches,
+/// a space or backslash is escaped with a backslash. Since matched characters are only
+/// counted, the diff is short where the strings have much in common, but it can only be
+/// decoded together with the original string.
+#[must_use]
+pub fn encode_diff(script: &[EditOp]) -> String {
+    let mut out = String::with_capacity(script.len());
+    let mut prev_sigil: Option<char> = None;
+    let mut matches = 0;
+
+    for &op in script {
+        let (sigil, chars) = match op {
+            EditOp::Match(_) => ('=', [None, None]),
+            EditOp::Delete(c) => ('-', [Some(c), None]),
+            EditOp::Insert(c) => ('+', [Some(c), None]),
+            EditOp::Substitute { from, to } => ('~', [Some(from), Some(to)]),
+        };
+
+        // Runs of the same operation share a token, except for substitutions
+        if sigil == '~' || prev_sigil != Some(sigil) {
+            if prev_sigil == Some('=') {
+                push_count(&mut out, matches);
+                matches = 0;
+            }
+            if prev_sigil.is_some() {
+                out.push(' ');
+            }
+            out.push(sigil);
+        }
+        if sigil == '=' {
+            matches += 1;
+        }
+        for c in chars.into_iter().flatten() {
+            if matches!(c, ' ' | '\\') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+
+        prev_sigil = Some(sigil);
+    }
+
+    if prev_sigil == Some('=') {
+        push_count(&mut out, matches);
+    }
+    out
+}
+
+fn push_count(out: &mut String, count: usize) {
+    use core::fmt::Write;
+    // Writing to a `String` can't fail
+    let _ = write!(out, "{count}");
+}
+
+/// Returns the edit script encoded by `diff`, in the format of `encode_diff`, for the
+/// string `a` to which it applies
+///
+/// # Errors
+///
+/// Returns `ParseError`, with the byte offset of the problem, if `diff` is not a valid
+/// encoding: e.g., an unknown sigil, a token with no characters (as from two spaces in a
+/// row), a match count that isn't a positive number, a substitution of other than two
+/// characters, or a backslash not escaping a space or another backslash. It is also
+/// returned if `diff` doesn't fit `a`: if it matches, deletes, or substitutes characters
+/// other than those of `a`, in order, or doesn't account for all of them (in which case
+/// the offset is the length of `diff`).
+pub fn decode_diff(a: &str, diff: &str) -> Result<Vec<EditOp>, ParseError> {
+    let mut script: Vec<EditOp> = Vec::new();
+    let mut source = a.chars();
+    if diff.is_empty() {
+        return if source.next().is_none() {
+            Ok(script)
+        } else {
+            Err(ParseError { offset: 0 })
+        };
+    }
+
+    let mut chars = diff.char_indices();
+    let mut payload: Vec<char> = Vec::new();
+
+    loop {
+        let Some((start, sigil)) = chars.next() else {
+            // A trailing space
+            return Err(ParseError { offset: diff.len() });
+        };
+
+        payload.clear();
+        let mut last = true;
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                ' ' => {
+                    last = false;
+                    break;
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped @ (' ' | '\\'))) => payload.push(escaped),
+                    _ => return Err(ParseError { offset }),
+                },
+                _ => payload.push(c),
+            }
+        }
+
+        let error = ParseError { offset: start };
+        // The next character of `a`, which must be `c` if given
+        let mut take = |c: Option<char>| {
+            source
+                .next()
+                .filter(|&next| c.is_none_or(|c| c == next))
+                .ok_or(error)
+        };
+
+        match (sigil, payload.as_slice()) {
+            (_, []) => return Err(error),
+            ('=', digits) => {
+                let count: usize = String::from_iter(digits).parse().map_err(|_| error)?;
+                if count == 0 || digits[0] == '+' {
+                    return Err(error);
+                }
+                for _ in 0..count {
+                    script.push(EditOp::Match(take(None)?));
+                }
+            }
+            ('-', run) => {
+                for &c in run {
+                    script.push(EditOp::Delete(take(Some(c))?));
+                }
+            }
+            ('+', run) => script.extend(run.iter().map(|&c| EditOp::Insert(c))),
+            ('~', &[from, to]) => {
+                take(Some(from))?;
+                script.push(EditOp::Substitute { from, to });
+            }
+            _ => return Err(error),
+        }
+
+        if last {
+            break;
+        }
+    }
+
+    if source.next().is_some() {
+        return Err(ParseError { offset: diff.len() });
+    }
+    Ok(script)
+}
+
+/// Returns `a` and `b` aligned for side-by-side display, with the `gap` character
+/// inserted at the positions of insertions and deletions. The `ascii` flag works as for
+/// `edit_script`.
+///
+/// Both results have the same length in `char`s, and each position holds either a pair
+/// of matching or substituted characters, or a character facing a gap. Removing the gaps
+/// recovers the original strings (in ASCII mode, provided they are ASCII-only).
+#[must_use]
+pub fn align(a: &str, b: &str, gap: char, ascii: bool) -> (String, String) {
+    let mut a_out = String::with_capacity(a.len());
+    let mut b_out = String::with_capacity(b.len());
+
+    for op in edit_script(a, b, ascii) {
+        let (x, y) = match op {
+            EditOp::Insert(c) => (gap, c),
+            EditOp::Delete(c) => (c, gap),
+            EditOp::Substitute { from, to } => (from, to),
+            EditOp::Match(c) => (c, c),
+        };
+        a_out.push(x);
+        b_out.push(y);
+    }
+
+    (a_out, b_out)
+}
+
+/// Returns a three-line, human-readable alignment of `a` and `b`, for logging and
+/// debugging. The `ascii` flag works as for `edit_script`.
+///
+/// The first and last lines are `a` and `b` as given by `align`, with `-` for gaps. The
+/// middle line marks each matching pair of characters with `|`, leaving a space under
+/// substitutions and gaps:
+///
+/// ```
+/// use agnostic_levenshtein::format_alignment;
+///
+/// assert_eq!(
+///     format_alignment("sitting", "kitten", true),
+///     "sitting\n ||| | \nkitten-"
+/// );
+/// ```
+///
+/// The columns line up only if every character is displayed at the same width, as with
+/// ASCII text in a monospace font.
+#[must_use]
+pub fn format_alignment(a: &str, b: &str, ascii: bool) -> String {
+    let script = edit_script(a, b, ascii);
+    let mut top = String::with_capacity(script.len());
+    let mut markers = String::with_capacity(script.len());
+    let mut bottom = String::with_capacity(script.len());
+
+    for op in script {
+        let (x, marker, y) = match op {
+            EditOp::Insert(c) => ('-', ' ', c),
+            EditOp::Delete(c) => (c, ' ', '-'),
+            EditOp::Substitute { from, to } => (from, ' ', to),
+            EditOp::Match(c) => (c, '|', c),
+        };
+        top.push(x);
+        markers.push(marker);
+        bottom.push(y);
+    }
+
+    [top, markers, bottom].join("\n")
+}
+
+/// Returns the complete `(m + 1) × (n + 1)` DP table for two strings, `a` and `b`, of
+/// lengths `m` and `n`. The `ascii` flag works as for `edit_distance`.
+///
+/// Cell `[i][j]` holds the distance between the first `i` units of `a` and the first `j`
+/// of `b`, so the bottom-right cell is the distance between the full strings. This is
+/// mainly for inspection and teaching: the whole table is kept in memory, unlike in
+/// `edit_distance`, which needs only two rows at a time.
+#[must_use]
+pub fn distance_matrix(a: &str, b: &str, ascii: bool) -> Vec<Vec<u32>> {
+    if ascii {
+        full_matrix(a.as_bytes(), b.as_bytes())
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        full_matrix(&a_chars, &b_chars)
+    }
+}
+
+/// Returns the complete DP table, where `[i][j]` is the distance between the first `i`
+/// elements of `a` and the first `j` elements of `b`
+fn full_matrix<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<u32>> {
+    let mut dp: Vec<Vec<u32>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i as u32;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j as u32;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            if a[i - 1] == b[j - 1] {
+                dp[i][j] = dp[i - 1][j - 1];
+                continue;
+            }
+
+            let insert = dp[i][j - 1] + 1;
+            let delete = dp[i - 1][j] + 1;
+            let substitute = dp[i - 1][j - 1] + 1;
+
+            dp[i][j] = insert.min(delete).min(substitute);
+        }
+    }
+
+    dp
+}
+
+fn backtrack<T: PartialEq + Copy + Into<char>>(
+    a: &[T],
+    b: &[T],
+    tie_break: TieBreak,
+) -> Vec<EditOp> {
+    let dp = full_matrix(a, b);
+    let mut ops: Vec<EditOp> = Vec::with_capacity(a.len().max(b.len()));
+
+    let order = match tie_break {
+        TieBreak::PreferSubstitution => [Step::Substitute, Step::Delete, Step::Insert],
+        TieBreak::PreferDeletion => [Step::Delete, Step::Substitute, Step::Insert],
+        TieBreak::PreferInsertion => [Step::Insert, Step::Substitute, Step::Delete],
+    };
+
+    // Walk back from the bottom-right corner, always taking a match if there is one
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            ops.push(EditOp::Match(a[i - 1].into()));
+            i -= 1;
+            j -= 1;
+            continue;
+        }
+
+        // Any move that accounts for the value of the cell is on an optimal path
+        let step = order.into_iter().find(|step| match step {
+            Step::Substitute => i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + 1,
+            Step::Delete => i > 0 && dp[i][j] == dp[i - 1][j] + 1,
+            Step::Insert => j > 0 && dp[i][j] == dp[i][j - 1] + 1,
+        });
+
+        match step {
+            Some(Step::Substitute) => {
+                ops.push(EditOp::Substitute {
+                    from: a[i - 1].into(),
+                    to: b[j - 1].into(),
+                });
+                i -= 1;
+                j -= 1;
+            }
+            Some(Step::Delete) => {
+                ops.push(EditOp::Delete(a[i - 1].into()));
+                i -= 1;
+            }
+            Some(Step::Insert) | None => {
+                ops.push(EditOp::Insert(b[j - 1].into()));
+                j -= 1;
+            }
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// A move back through the DP matrix, other than a match
+#[derive(Clone, Copy)]
+enum Step {
+    Substitute,
+    Delete,
+    Insert,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    fn edit_count(script: &[EditOp]) -> u32 {
+        script
+            .iter()
+            .filter(|op| !matches!(op, EditOp::Match(_)))
+            .count() as u32
+    }
+
+    #[test]
+    fn applied_round_trip() {
+        let pairs = [
+            ("sitting", "kitten"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("", "شاهنامه"),
+            ("شاهنامه", ""),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(apply_script(a, &edit_script(a, b, false)).as_deref(), Ok(b));
+        }
+        assert_eq!(
+            apply_script("kitten", &edit_script("kitten", "sitting", true)).as_deref(),
+            Ok("sitting")
+        );
+    }
+
+    #[test]
+    fn applied_mismatch() {
+        let script = edit_script("sitting", "kitten", true);
+        // The first operation substitutes "s"
+        assert_eq!(
+            apply_script("bitting", &script),
+            Err(ScriptMismatch { index: 0 })
+        );
+        assert_eq!(
+            apply_script("sitting!", &script),
+            Err(ScriptMismatch { index: 7 })
+        );
+        assert_eq!(
+            apply_script("sittin", &script),
+            Err(ScriptMismatch { index: 6 })
+        );
+        assert_eq!(apply_script("", &[]).as_deref(), Ok(""));
+    }
+
+    #[test]
+    fn tie_break_policies() {
+        let scripts: Vec<Vec<EditOp>> = [
+            TieBreak::PreferSubstitution,
+            TieBreak::PreferInsertion,
+            TieBreak::PreferDeletion,
+        ]
+        .into_iter()
+        .map(|tie_break| edit_script_with("ab", "ba", true, tie_break))
+        .collect();
+
+        assert_eq!(
+            scripts[0],
+            [
+                EditOp::Substitute { from: 'a', to: 'b' },
+                EditOp::Substitute { from: 'b', to: 'a' }
+            ]
+        );
+        assert_eq!(
+            scripts[1],
+            [EditOp::Delete('a'), EditOp::Match('b'), EditOp::Insert('a')]
+        );
+        assert_eq!(
+            scripts[2],
+            [EditOp::Insert('b'), EditOp::Match('a'), EditOp::Delete('b')]
+        );
+
+        // Different, but all minimal and valid
+        for script in &scripts {
+            assert_eq!(edit_count(script), 2);
+            assert_eq!(apply_script("ab", script).as_deref(), Ok("ba"));
+        }
+    }
+
+    #[test]
+    fn tie_break_unicode() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        for tie_break in [TieBreak::PreferInsertion, TieBreak::PreferDeletion] {
+            let script = edit_script_with(a, b, false, tie_break);
+            assert_eq!(edit_count(&script), edit_distance(a, b, false));
+            assert_eq!(apply_script(a, &script).as_deref(), Ok(b));
+        }
+        assert_eq!(
+            edit_script_with(a, b, false, TieBreak::default()),
+            edit_script(a, b, false)
+        );
+    }
+
+    #[test]
+    fn sitting_kitten_script() {
+        let script = edit_script("sitting", "kitten", true);
+        assert_eq!(apply_script("sitting", &script).as_deref(), Ok("kitten"));
+        assert_eq!(edit_count(&script), 3);
+    }
+
+    #[test]
+    fn positions_sitting_kitten() {
+        // The sides swapped: "k" and "e" are substituted, and "g" inserted
+        assert_eq!(
+            edit_positions("kitten", "sitting", false),
+            [
+                (0, EditOp::Substitute { from: 'k', to: 's' }),
+                (4, EditOp::Substitute { from: 'e', to: 'i' }),
+                (6, EditOp::Insert('g')),
+            ]
+        );
+        assert_eq!(
+            edit_positions("ʿAlī", "Alīʿ", false),
+            [(0, EditOp::Delete('ʿ')), (3, EditOp::Insert('ʿ'))]
+        );
+        assert!(edit_positions("kitten", "kitten", true).is_empty());
+    }
+
+    #[test]
+    fn index_map_insertion() {
+        // "ibn" inserted in the middle
+        assert_eq!(
+            index_map("ʿAlī Ṭālib", "ʿAlī ibn Ṭālib", false),
+            [0, 1, 2, 3, 8, 9, 10, 11, 12, 13].map(Some)
+        );
+        assert_eq!(
+            index_map("sitting", "kitten", true),
+            [Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), None]
+        );
+        assert_eq!(index_map("kitten", "", true), [None; 6]);
+        assert!(index_map("", "kitten", true).is_empty());
+    }
+
+    #[test]
+    fn diff_round_trip() {
+        let pairs = [
+            ("sitting", "kitten"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("a b\\c", "a  b\\\\"),
+            ("", "kitten"),
+            ("kitten", ""),
+            ("", ""),
+        ];
+        for (a, b) in pairs {
+            for tie_break in [TieBreak::PreferSubstitution, TieBreak::PreferInsertion] {
+                let script = edit_script_with(a, b, false, tie_break);
+                assert_eq!(decode_diff(a, &encode_diff(&script)), Ok(script));
+            }
+        }
+
+        assert_eq!(
+            encode_diff(&edit_script("ab ", "b\\", false)),
+            r"-a =1 ~\ \\"
+        );
+        assert_eq!(encode_diff(&edit_script("kitten", "", true)), "-kitten");
+
+        // Long matching runs take a few characters
+        let a = "ʿAlī ibn Abī Ṭālib ".repeat(10);
+        let b = format!("{a}!");
+        assert_eq!(encode_diff(&edit_script(&a, &b, false)), "=190 +!");
+        assert_eq!(encode_diff(&edit_script("", "", false)), "");
+    }
+
+    #[test]
+    fn diff_errors() {
+        for (diff, offset) in [
+            ("=2  +c", 3),
+            ("=2 ", 3),
+            (" =2", 0),
+            ("=2 *c", 3),
+            ("~abc", 0),
+            ("~a", 0),
+            ("=", 0),
+            ("=0", 0),
+            ("=x", 0),
+            ("=+2", 0),
+            (r"+a\b", 2),
+            ("+a\\", 2),
+            // Not fitting "ab" (with the encoding otherwise valid)
+            ("=3", 0),
+            ("=1", 2),
+            ("-ax", 0),
+            ("=1 ~ab", 3),
+            ("", 0),
+        ] {
+            assert_eq!(
+                decode_diff("ab", diff),
+                Err(ParseError { offset }),
+                "{diff:?}"
+            );
+        }
+        assert_eq!(decode_diff("", ""), Ok(Vec::new()));
+        assert_eq!(
+            decode_diff("ab", "-a ~bc"),
+            Ok(Vec::from([
+                EditOp::Delete('a'),
+                EditOp::Substitute { from: 'b', to: 'c' }
+            ]))
+        );
+    }
+
+    #[test]
+    fn replay_unicode() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        let script = edit_script(a, b, false);
+        assert_eq!(apply_script(a, &script).as_deref(), Ok(b));
+        assert_eq!(edit_count(&script), edit_distance(a, b, false));
+    }
+
+    #[test]
+    fn distance_result() {
+        let result = DistanceResult::from(edit_script("sitting", "kitten", false));
+        assert_eq!(result.distance, 3);
+        assert_eq!(result.ops.len(), 7);
+        assert_eq!(DistanceResult::from(Vec::new()).distance, 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let result = DistanceResult::from(edit_script("ʿAlī", "ʿUlā", false));
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            json,
+            r#"{"distance":2,"ops":[{"Match":"ʿ"},{"Substitute":{"from":"A","to":"U"}},{"Match":"l"},{"Substitute":{"from":"ī","to":"ā"}}]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<DistanceResult>(&json).unwrap(),
+            result
+        );
+
+        // A `char` must be a single character
+        assert!(serde_json::from_str::<EditOp>(r#"{"Insert":"ab"}"#).is_err());
+    }
+
+    #[test]
+    fn aligned_sitting_kitten() {
+        let (a, b) = align("sitting", "kitten", '-', true);
+        assert_eq!(a.chars().count(), b.chars().count());
+        assert_eq!(a.replace('-', ""), "sitting");
+        assert_eq!(b.replace('-', ""), "kitten");
+        assert_eq!((a.as_str(), b.as_str()), ("sitting", "kitten-"));
+    }
+
+    #[test]
+    fn aligned_unicode() {
+        let (a, b) = align("شاهنامه", "شهنامه", '_', false);
+        assert_eq!(a, "شاهنامه");
+        assert_eq!(b, "ش_هنامه");
+    }
+
+    #[test]
+    fn formatted_sitting_kitten() {
+        let formatted = format_alignment("sitting", "kitten", true);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].matches('|').count(), 4);
+        let (a, b) = align("sitting", "kitten", '-', true);
+        assert_eq!((lines[0], lines[2]), (a.as_str(), b.as_str()));
+    }
+
+    #[test]
+    fn formatted_gaps() {
+        assert_eq!(
+            format_alignment("maḥmūd", "mamūd", false),
+            "maḥmūd\n|| |||\nma-mūd"
+        );
+        assert_eq!(format_alignment("", "ab", true), "--\n  \nab");
+        assert_eq!(format_alignment("", "", true), "\n\n");
+    }
+
+    #[test]
+    fn script_empty() {
+        assert_eq!(
+            edit_script("", "ab", false),
+            vec![EditOp::Insert('a'), EditOp::Insert('b')]
+        );
+        assert_eq!(edit_script("a", "", true), vec![EditOp::Delete('a')]);
+        assert!(edit_script("", "", true).is_empty());
+    }
+
+    #[test]
+    fn matrix_shape_and_corner() {
+        let a = "maḥmūd";
+        let b = "muḥammad";
+        for ascii in [true, false] {
+            let matrix = distance_matrix(a, b, ascii);
+            let (m, n) = if ascii {
+                (a.len(), b.len())
+            } else {
+                (a.chars().count(), b.chars().count())
+            };
+
+            assert_eq!(matrix.len(), m + 1);
+            assert!(matrix.iter().all(|row| row.len() == n + 1));
+            assert_eq!(matrix[m][n], edit_distance(a, b, ascii));
+        }
+    }
+
+    #[test]
+    fn matrix_kitten_sitting() {
+        let matrix = distance_matrix("kitten", "sitting", true);
+        assert_eq!(matrix[0], [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(matrix[1], [1, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(matrix[6][7], 3);
+    }
+
+    #[test]
+    fn breakdown_sitting_kitten() {
+        let breakdown = edit_distance_breakdown("sitting", "kitten", true);
+        assert_eq!(
+            breakdown,
+            Breakdown {
+                insertions: 0,
+                deletions: 1,
+                substitutions: 2,
+                total: 3,
+            }
+        );
+
+        // The other way round, the deletion becomes an insertion
+        let breakdown = edit_distance_breakdown("kitten", "sitting", false);
+        assert_eq!((breakdown.insertions, breakdown.deletions), (1, 0));
+        assert_eq!(breakdown.substitutions, 2);
+    }
+
+    #[test]
+    fn breakdown_total() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        let breakdown = edit_distance_breakdown(a, b, false);
+        assert_eq!(breakdown.total, edit_distance(a, b, false));
+        assert_eq!(
+            breakdown.total,
+            breakdown.insertions + breakdown.deletions + breakdown.substitutions
+        );
+        assert_eq!(edit_distance_breakdown(a, a, false), Breakdown::default());
+    }
+}
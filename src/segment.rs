@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::edit_distance_slice;
+#[cfg(feature = "normalization")]
+use crate::prep::{NormalizeOpts, normalize};
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, treating
+/// each extended grapheme cluster as a single unit.
+///
+/// This matches user-perceived characters more closely than the `char`-based path: an
+/// emoji with modifiers, a flag, or a Devanagari conjunct counts as one unit, however
+/// many scalar values it comprises. Clusters are segmented by the `unicode-segmentation`
+/// crate, following Unicode Standard Annex #29. Requires the `segmentation` feature.
+#[must_use]
+pub fn edit_distance_graphemes(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let a_graphemes = graphemes(a);
+    let b_graphemes = graphemes(b);
+    edit_distance_slice(&a_graphemes, &b_graphemes)
+}
+
+/// Returns the Levenshtein distance (`u32`) between two strings, `a` and `b`, as seen by
+/// a reader: after NFC normalization, with each grapheme cluster as a single unit.
+///
+/// This combines `edit_distance_normalized` and `edit_distance_graphemes`, so that,
+/// e.g., a precomposed and a decomposed "é" are alike, and a ZWJ emoji sequence counts
+/// as one unit. Requires both the `normalization` and `segmentation` features.
+#[cfg(feature = "normalization")]
+#[must_use]
+pub fn edit_distance_visual(a: &str, b: &str) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let opts = NormalizeOpts {
+        nfc: true,
+        ..NormalizeOpts::default()
+    };
+    let (a, b) = (normalize(a, opts), normalize(b, opts));
+    edit_distance_slice(&graphemes(&a), &graphemes(&b))
+}
+
+/// Splits `s` into extended grapheme clusters
+fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn flags() {
+        let a = "\u{1F1FA}\u{1F1F8}";
+        let b = "\u{1F1EB}\u{1F1F7}";
+        assert_eq!(edit_distance(a, b, false), 2);
+        assert_eq!(edit_distance_graphemes(a, b), 1);
+        assert_eq!(edit_distance_graphemes(a, ""), 1);
+    }
+
+    #[test]
+    fn zwj_family() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(graphemes(family).len(), 1);
+        assert_eq!(edit_distance_graphemes(family, "\u{1F468}"), 1);
+        assert_eq!(edit_distance(family, "\u{1F468}", false), 4);
+    }
+
+    #[test]
+    fn devanagari() {
+        // "क्षत्रिय": since Unicode 15.1 (GB9c), conjuncts are not split after the virama
+        let kshatriya = "\u{915}\u{94D}\u{937}\u{924}\u{94D}\u{930}\u{93F}\u{92F}";
+        assert_eq!(
+            graphemes(kshatriya),
+            [
+                "\u{915}\u{94D}\u{937}",
+                "\u{924}\u{94D}\u{930}\u{93F}",
+                "\u{92F}"
+            ]
+        );
+        assert_eq!(
+            edit_distance_graphemes(kshatriya, "\u{915}\u{94D}\u{937}"),
+            2
+        );
+    }
+
+    #[test]
+    fn regional_indicator_pairs() {
+        let three = "\u{1F1FA}\u{1F1F8}\u{1F1EB}";
+        assert_eq!(graphemes(three), ["\u{1F1FA}\u{1F1F8}", "\u{1F1EB}"]);
+    }
+
+    #[test]
+    fn crlf_and_ascii() {
+        assert_eq!(graphemes("a\r\nb"), ["a", "\r\n", "b"]);
+        assert_eq!(edit_distance_graphemes("sitting", "kitten"), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "normalization")]
+    fn visual_accent_and_family() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let precomposed = format!("caf\u{E9} {family}");
+        let decomposed = format!("cafe\u{301} {family}");
+        assert_eq!(edit_distance_visual(&precomposed, &decomposed), 0);
+
+        let other = format!("cafe {family}{family}");
+        assert_eq!(edit_distance_visual(&precomposed, &other), 2);
+        assert_eq!(edit_distance_graphemes(&decomposed, &other), 2);
+    }
+}
@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+/// Returns an approximation of the Levenshtein distance between two strings, `a` and
+/// `b`, using the Sift4 heuristic. The `ascii` flag works as for `edit_distance`.
+///
+/// This is the "simplest" variant of Sift4: it walks both strings in step and, on a
+/// mismatch, looks up to `max_offset` positions ahead in each for the next match. It
+/// runs in linear time (for a fixed `max_offset`), but the result is **not** the true
+/// Levenshtein distance—it may be higher or lower. A larger `max_offset` tends to give a
+/// closer estimate at some cost in speed.
+#[must_use]
+pub fn sift4_distance(a: &str, b: &str, max_offset: usize, ascii: bool) -> u32 {
+    if ascii {
+        sift4(a.as_bytes(), b.as_bytes(), max_offset)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        sift4(&a_chars, &b_chars, max_offset)
+    }
+}
+
+fn sift4<T: PartialEq>(a: &[T], b: &[T], max_offset: usize) -> u32 {
+    if a.is_empty() {
+        return b.len() as u32;
+    }
+
+    if b.is_empty() {
+        return a.len() as u32;
+    }
+
+    // Cursors into `a` and `b`
+    let (mut x, mut y) = (0, 0);
+    // Length of the current run of matches, and the total of all runs so far
+    let mut run = 0;
+    let mut lcss = 0;
+
+    while x < a.len() && y < b.len() {
+        if a[x] == b[y] {
+            run += 1;
+        } else {
+            lcss += run;
+            run = 0;
+
+            if x != y {
+                x = x.max(y);
+                y = x;
+                if x >= a.len() || y >= b.len() {
+                    break;
+                }
+            }
+
+            // Realign at the nearest match within the window, if there is one
+            for offset in 0..max_offset {
+                if x + offset < a.len() && a[x + offset] == b[y] {
+                    x += offset;
+                    run += 1;
+                    break;
+                }
+
+                if y + offset < b.len() && a[x] == b[y + offset] {
+                    y += offset;
+                    run += 1;
+                    break;
+                }
+            }
+        }
+
+        x += 1;
+        y += 1;
+    }
+
+    lcss += run;
+    (a.len().max(b.len()) - lcss) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn equal_and_empty() {
+        assert_eq!(sift4_distance("kitten", "kitten", 5, true), 0);
+        assert_eq!(sift4_distance("", "kitten", 5, true), 6);
+        assert_eq!(sift4_distance("maḥmūd", "", 5, false), 6);
+    }
+
+    #[test]
+    fn close_to_exact() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("Firdawsi", "Ferdowsi"),
+            ("Ghiyāth al-Dīn", "Ghiyath ud-Din"),
+            ("the quick brown fox", "the quikc brown fax"),
+        ];
+
+        for (a, b) in pairs {
+            let exact = edit_distance(a, b, false);
+            let approx = sift4_distance(a, b, 5, false);
+            assert!(
+                approx.abs_diff(exact) <= 2,
+                "{a} / {b}: {approx} vs {exact}"
+            );
+        }
+    }
+
+    #[test]
+    fn cursor_past_end() {
+        assert_eq!(sift4_distance("abcxyz", "xyzabcdefgh", 5, true), 8);
+        assert_eq!(sift4_distance("xyzabcdefgh", "abcxyz", 5, true), 8);
+    }
+
+    #[test]
+    fn single_substitution() {
+        assert_eq!(sift4_distance("kitten", "sitten", 5, true), 1);
+    }
+}
@@ -0,0 +1,362 @@
+//! Similarity measures other than the Levenshtein-based `similarity_ratio`
+//!
+//! These return values from 0.0 (nothing in common) to 1.0 (identical), and are
+//! typically better suited than edit distance to comparing short strings such as names.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns the Jaro similarity of two strings, `a` and `b`, from 0.0 to 1.0. The `ascii`
+/// flag indicates whether the strings can be treated as ASCII-only.
+///
+/// This counts the characters the strings have in common within a window proportional
+/// to their length, as well as the transpositions among those characters.
+#[must_use]
+pub fn jaro_similarity(a: &str, b: &str, ascii: bool) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    if ascii {
+        jaro(a.as_bytes(), b.as_bytes())
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        jaro(&a_chars, &b_chars)
+    }
+}
+
+/// Returns the Jaro-Winkler similarity of two strings, `a` and `b`, from 0.0 to 1.0. The
+/// `ascii` flag works as for `jaro_similarity`.
+///
+/// This boosts the Jaro similarity in proportion to the length of the common prefix (up
+/// to four characters), scaled by `prefix_weight`. The conventional weight is 0.1; it
+/// should not exceed 0.25, or the result may exceed 1.0.
+#[must_use]
+pub fn jaro_winkler_similarity(a: &str, b: &str, ascii: bool, prefix_weight: f64) -> f64 {
+    let jaro = jaro_similarity(a, b, ascii);
+
+    let prefix = if ascii {
+        common_prefix(a.bytes(), b.bytes())
+    } else {
+        common_prefix(a.chars(), b.chars())
+    };
+
+    jaro + f64::from(prefix) * prefix_weight * (1.0 - jaro)
+}
+
+fn jaro<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    // We already know: strings are not equal
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Characters only match if they're no further apart than this
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches: u32 = 0;
+
+    for (i, a_char) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+
+        for j in lo..hi {
+            if !b_matched[j] && b[j] == *a_char {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Matched characters that appear in a different order in each string
+    let a_seq = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let b_seq = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let half_transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() as u32;
+
+    let m = f64::from(matches);
+    let t = f64::from(half_transpositions) / 2.0;
+    let (a_len, b_len) = (f64::from(a.len() as u32), f64::from(b.len() as u32));
+
+    (m / a_len + m / b_len + (m - t) / m) / 3.0
+}
+
+/// Returns the Sørensen-Dice coefficient of the character trigrams of two strings, `a`
+/// and `b`, from 0.0 to 1.0. Strings are compared as sequences of `char`s.
+///
+/// Each string is padded with two spaces at the start and one at the end, so that even
+/// short strings have trigrams, and those at the edges weigh a little more. The result
+/// is `2 * |A ∩ B| / (|A| + |B|)` for the sets of trigrams `A` and `B`. Since it depends
+/// only on which trigrams occur, not where, it is much less sensitive than edit
+/// distance to the reordering of words.
+#[must_use]
+pub fn dice_trigram_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let a_trigrams = trigrams(a);
+    let b_trigrams = trigrams(b);
+    let common = a_trigrams.intersection(&b_trigrams).count() as u32;
+    let total = (a_trigrams.len() + b_trigrams.len()) as u32;
+
+    2.0 * f64::from(common) / f64::from(total)
+}
+
+/// Returns the similarity of two strings, `a` and `b`, from 0.0 to 1.0, as given by
+/// Python's `difflib.SequenceMatcher`.
+///
+/// The result is exactly that of `SequenceMatcher(None, a, b).ratio()`. The `ascii` flag
+/// works as for `jaro_similarity`.
+///
+/// This is *not* based on edit distance, and differs from `similarity_ratio`: it is
+/// `2 * M / T`, where `T` is the total length of the strings and `M` the number of
+/// characters in the "matching blocks" found by taking the longest common substring,
+/// then recursing to either side of it. E.g., "abcd" and "bcde" share the block "bcd",
+/// for a ratio of 0.75, whereas their edit distance of 2 gives a `similarity_ratio` of
+/// 0.5.
+///
+/// Like Python's implementation, this treats characters that make up more than 1% of
+/// `b` as "junk" if `b` is at least 200 characters long: a match can't begin with them,
+/// though it can be extended over them. The result is therefore not always symmetric.
+#[must_use]
+pub fn difflib_ratio(a: &str, b: &str, ascii: bool) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let (matched, total) = if ascii {
+        (
+            matching_blocks(a.as_bytes(), b.as_bytes()),
+            a.len() + b.len(),
+        )
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let matched = matching_blocks(&a_chars, &b_chars);
+        (matched, a_chars.len() + b_chars.len())
+    };
+
+    2.0 * f64::from(matched as u32) / f64::from(total as u32)
+}
+
+/// Total size of the matching blocks of `a` and `b`, as in `SequenceMatcher`
+fn matching_blocks<T: Ord>(a: &[T], b: &[T]) -> usize {
+    // Positions in `b` at which each element occurs, minus the "popular" ones
+    let mut b2j: BTreeMap<&T, Vec<usize>> = BTreeMap::new();
+    for (j, elem) in b.iter().enumerate() {
+        b2j.entry(elem).or_default().push(j);
+    }
+    if b.len() >= 200 {
+        let popular = b.len() / 100 + 1;
+        b2j.retain(|_, js| js.len() <= popular);
+    }
+
+    let mut matched = 0;
+    let mut queue: Vec<(usize, usize, usize, usize)> = Vec::from([(0, a.len(), 0, b.len())]);
+
+    while let Some((a_lo, a_hi, b_lo, b_hi)) = queue.pop() {
+        let (a_start, b_start, len) = longest_match(a, b, &b2j, (a_lo, a_hi), (b_lo, b_hi));
+        if len == 0 {
+            continue;
+        }
+
+        matched += len;
+        if a_lo < a_start && b_lo < b_start {
+            queue.push((a_lo, a_start, b_lo, b_start));
+        }
+        if a_start + len < a_hi && b_start + len < b_hi {
+            queue.push((a_start + len, a_hi, b_start + len, b_hi));
+        }
+    }
+
+    matched
+}
+
+/// Start in `a`, start in `b`, and length of the longest block common to `a[a_lo..a_hi]`
+/// and `b[b_lo..b_hi]`, as in `SequenceMatcher.find_longest_match`: the earliest in `a`,
+/// then in `b`, of those of maximal length
+fn longest_match<T: Ord>(
+    a: &[T],
+    b: &[T],
+    b2j: &BTreeMap<&T, Vec<usize>>,
+    (a_lo, a_hi): (usize, usize),
+    (b_lo, b_hi): (usize, usize),
+) -> (usize, usize, usize) {
+    let (mut best_i, mut best_j, mut best_k) = (a_lo, b_lo, 0);
+
+    // Length of the match ending at each position in `b`, for the previous element of `a`
+    let mut j2len: BTreeMap<usize, usize> = BTreeMap::new();
+
+    for (i, elem) in a.iter().enumerate().take(a_hi).skip(a_lo) {
+        let mut new_j2len: BTreeMap<usize, usize> = BTreeMap::new();
+
+        for &j in b2j.get(elem).into_iter().flatten() {
+            if j < b_lo {
+                continue;
+            }
+            if j >= b_hi {
+                break;
+            }
+
+            let k = j
+                .checked_sub(1)
+                .and_then(|prev| j2len.get(&prev))
+                .map_or(1, |k| k + 1);
+            new_j2len.insert(j, k);
+
+            if k > best_k {
+                (best_i, best_j, best_k) = (i + 1 - k, j + 1 - k, k);
+            }
+        }
+
+        j2len = new_j2len;
+    }
+
+    // Popular elements can't start a match, but they can extend one on either side
+    let before = a[a_lo..best_i]
+        .iter()
+        .rev()
+        .zip(b[b_lo..best_j].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (best_i, best_j, best_k) = (best_i - before, best_j - before, best_k + before);
+
+    let after = a[best_i + best_k..a_hi]
+        .iter()
+        .zip(&b[best_j + best_k..b_hi])
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    (best_i, best_j, best_k + after)
+}
+
+fn trigrams(s: &str) -> BTreeSet<[char; 3]> {
+    let padded: Vec<char> = "  ".chars().chain(s.chars()).chain([' ']).collect();
+    padded.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Length of the common prefix, up to the four characters that Jaro-Winkler considers
+fn common_prefix<T: PartialEq>(a: impl Iterator<Item = T>, b: impl Iterator<Item = T>) -> u32 {
+    a.zip(b).take(4).take_while(|(x, y)| x == y).count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(x: f64, y: f64) -> bool {
+        (x - y).abs() < 1e-3
+    }
+
+    #[test]
+    fn martha_marhta() {
+        assert!(approx(jaro_similarity("MARTHA", "MARHTA", true), 0.944));
+        assert!(approx(
+            jaro_winkler_similarity("MARTHA", "MARHTA", true, 0.1),
+            0.961
+        ));
+    }
+
+    #[test]
+    fn reference_values() {
+        assert!(approx(jaro_similarity("DWAYNE", "DUANE", true), 0.822));
+        assert!(approx(
+            jaro_winkler_similarity("DWAYNE", "DUANE", false, 0.1),
+            0.840
+        ));
+        assert!(approx(jaro_similarity("DIXON", "DICKSONX", false), 0.767));
+        assert!(approx(
+            jaro_winkler_similarity("DIXON", "DICKSONX", true, 0.1),
+            0.813
+        ));
+    }
+
+    #[test]
+    fn identical_and_disjoint() {
+        assert!(approx(jaro_similarity("", "", true), 1.0));
+        assert!(approx(
+            jaro_winkler_similarity("maḥmūd", "maḥmūd", false, 0.1),
+            1.0
+        ));
+        assert!(approx(jaro_similarity("abc", "xyz", true), 0.0));
+        assert!(approx(jaro_similarity("abc", "", false), 0.0));
+    }
+
+    #[test]
+    fn unicode_mode() {
+        let ascii = jaro_similarity("maḥmūd", "mahmud", true);
+        let unicode = jaro_similarity("maḥmūd", "mahmud", false);
+        assert!(approx(unicode, (4.0 / 6.0 * 2.0 + 1.0) / 3.0));
+        assert!(ascii < unicode);
+    }
+
+    #[test]
+    fn night_nacht() {
+        // Two of six trigrams in common: "  n" and "ht "
+        let dice = dice_trigram_similarity("night", "nacht");
+        assert!(approx(dice, 1.0 / 3.0));
+        assert!(approx(crate::similarity_ratio("night", "nacht", true), 0.6));
+    }
+
+    #[test]
+    fn reordered_words() {
+        let a = "Abū al-Qāsim Firdawsī";
+        let b = "Firdawsī Abū al-Qāsim";
+        assert!(dice_trigram_similarity(a, b) > 0.7);
+        assert!(crate::similarity_ratio(a, b, false) < 0.3);
+    }
+
+    #[test]
+    fn dice_empty() {
+        assert!(approx(dice_trigram_similarity("", ""), 1.0));
+        assert!(approx(dice_trigram_similarity("night", ""), 0.0));
+        assert!(approx(dice_trigram_similarity("ab", "xy"), 0.0));
+    }
+
+    #[test]
+    fn difflib_reference_values() {
+        // As given by Python's `difflib.SequenceMatcher(None, a, b).ratio()`
+        assert!(approx(difflib_ratio("abcd", "bcde", true), 0.75));
+        assert!(approx(difflib_ratio("tide", "diet", false), 0.25));
+        assert!(approx(difflib_ratio("", "", true), 1.0));
+        assert!(approx(difflib_ratio("abc", "", true), 0.0));
+        assert!(approx(crate::similarity_ratio("abcd", "bcde", true), 0.5));
+    }
+
+    #[test]
+    fn difflib_unicode_and_junk() {
+        // Blocks "ma", "m", and "d"
+        assert!(approx(difflib_ratio("maḥmūd", "mahmud", false), 8.0 / 12.0));
+        assert!(approx(difflib_ratio("maḥmūd", "mahmud", true), 8.0 / 15.0));
+
+        // Every character of `b` is popular, so a match can only be extended from the start
+        let b = "a".repeat(300);
+        assert!(approx(difflib_ratio(&b, &b, true), 1.0));
+        assert!(approx(difflib_ratio(&format!("x{b}"), &b, true), 0.0));
+
+        let long = format!("{b}y");
+        assert!(approx(difflib_ratio("xa", &long, true), 0.0));
+        assert!(approx(difflib_ratio(&long, "xa", true), 2.0 / 303.0));
+    }
+}
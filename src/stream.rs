@@ -0,0 +1,187 @@
+use std::io::{self, BufRead, ErrorKind};
+
+use crate::min_distance;
+
+/// Returns the Levenshtein distance between the texts read from `a` and `b`, compared
+/// as sequences of `char`s, without holding the longer one in memory
+///
+/// Both readers are read a `char` at a time, in step, until one runs out. That one is
+/// the shorter text, and indexes the DP rows; the rest of the longer text is then
+/// streamed through them. So memory use is proportional to the length of the shorter
+/// text (about twice that, in fact, for the part of the longer one read in step), and
+/// either reader may be a file of any size. Requires the `std` feature.
+///
+/// # Errors
+///
+/// Returns any error from reading, including `ErrorKind::InvalidData` if either text is
+/// not valid UTF-8.
+pub fn edit_distance_streaming<A: BufRead, B: BufRead>(a: A, b: B) -> io::Result<u32> {
+    let mut a = Utf8Chars::new(a);
+    let mut b = Utf8Chars::new(b);
+    let mut a_head: Vec<char> = Vec::new();
+    let mut b_head: Vec<char> = Vec::new();
+
+    loop {
+        match (a.next().transpose()?, b.next().transpose()?) {
+            (Some(x), Some(y)) => {
+                a_head.push(x);
+                b_head.push(y);
+            }
+            (None, Some(y)) => {
+                b_head.push(y);
+                return stream_rest(&a_head, b_head, b);
+            }
+            (Some(x), None) => {
+                a_head.push(x);
+                return stream_rest(&b_head, a_head, a);
+            }
+            (None, None) => return Ok(min_distance(&a_head, b_head)),
+        }
+    }
+}
+
+/// The distance between `short` and the longer text, of which `head` has been read and
+/// `rest` remains
+fn stream_rest<R: BufRead>(short: &[char], head: Vec<char>, rest: Utf8Chars<R>) -> io::Result<u32> {
+    // The DP can't stop for an error, so the stream just ends early, and the error is
+    // reported afterward
+    let mut error: Option<io::Error> = None;
+    let long = head
+        .into_iter()
+        .chain(rest.map_while(|c| c.map_err(|e| error = Some(e)).ok()));
+
+    let dist = min_distance(short, long);
+    error.map_or(Ok(dist), Err)
+}
+
+/// The `char`s of a UTF-8 text, decoded from a reader one buffer at a time
+struct Utf8Chars<R> {
+    reader: R,
+    // The decoded text of the last buffer, and how far into it we've read
+    chunk: String,
+    pos: usize,
+    // Bytes not yet decoded, i.e., a sequence split across buffers
+    pending: Vec<u8>,
+}
+
+impl<R: BufRead> Utf8Chars<R> {
+    const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chunk: String::new(),
+            pos: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Decodes the next buffer from the reader into `chunk`, returning `false` at the end
+    fn refill(&mut self) -> io::Result<bool> {
+        self.chunk.clear();
+        self.pos = 0;
+
+        let bytes = loop {
+            match self.reader.fill_buf() {
+                Ok(bytes) => break bytes,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        };
+
+        if bytes.is_empty() {
+            return if self.pending.is_empty() {
+                Ok(false)
+            } else {
+                Err(invalid_utf8())
+            };
+        }
+
+        self.pending.extend_from_slice(bytes);
+        let len = bytes.len();
+        self.reader.consume(len);
+
+        let valid = match core::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // An incomplete sequence at the end may be completed by the next buffer
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => return Err(invalid_utf8()),
+        };
+
+        self.chunk
+            .push_str(core::str::from_utf8(&self.pending[..valid]).unwrap_or_default());
+        self.pending.drain(..valid);
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Iterator for Utf8Chars<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.chunk[self.pos..].chars().next() {
+                self.pos += c.len_utf8();
+                return Some(Ok(c));
+            }
+
+            match self.refill() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn cursors_match_in_memory() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("sitting", "kitten"),
+            ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān"),
+            ("", "شاهنامه"),
+            ("شاهنامه", ""),
+            ("", ""),
+        ];
+
+        for (a, b) in pairs {
+            let dist = edit_distance_streaming(Cursor::new(a), Cursor::new(b)).unwrap();
+            assert_eq!(dist, edit_distance(a, b, false));
+        }
+    }
+
+    #[test]
+    fn split_sequences() {
+        // One-byte buffers split every multibyte `char`
+        let a = "maḥmūd ".repeat(50);
+        let b = "muḥammad ".repeat(40);
+        let dist = edit_distance_streaming(
+            BufReader::with_capacity(1, Cursor::new(&a)),
+            BufReader::with_capacity(3, Cursor::new(&b)),
+        )
+        .unwrap();
+        assert_eq!(dist, edit_distance(&a, &b, false));
+    }
+
+    #[test]
+    fn invalid_utf8_reported() {
+        for (a, b) in [
+            (&b"kitten"[..], &b"sitt\xFFing"[..]),
+            (b"kitten", b"sitting\xE1\xB8"),
+            (b"caf\xE9", b"kitten"),
+        ] {
+            let err = edit_distance_streaming(Cursor::new(a), Cursor::new(b)).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+    }
+}
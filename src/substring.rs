@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use core::mem::swap;
+
+/// Finds the window of `text` that most closely matches `query`. The `ascii` flag
+/// indicates whether the strings can be treated as ASCII-only.
+///
+/// Returns the start and end indices of the window (end exclusive), in bytes for ASCII,
+/// `char`s otherwise, along with its Levenshtein distance from `query`. Skipping text
+/// before or after the window is free, so the match may lie anywhere in `text`. Among
+/// equally close windows, the one ending earliest is reported; of those ending there,
+/// which start is kept depends on the order in which the DP breaks ties, so the window
+/// need not be the one closest in length to `query`.
+#[must_use]
+pub fn best_substring_match(query: &str, text: &str, ascii: bool) -> (usize, usize, u32) {
+    if ascii {
+        substring_match(query.as_bytes(), text.as_bytes())
+    } else {
+        let query_chars: Vec<char> = query.chars().collect();
+        let text_chars: Vec<char> = text.chars().collect();
+        substring_match(&query_chars, &text_chars)
+    }
+}
+
+fn substring_match<T: PartialEq>(query: &[T], text: &[T]) -> (usize, usize, u32) {
+    let n = text.len();
+
+    // Each cell holds a cost and the text index at which its alignment starts. The first
+    // row is all zeros: the match may begin at any point in the text.
+    let mut dp_prev: Vec<(u32, usize)> = (0..=n).map(|j| (0, j)).collect();
+    let mut dp_curr: Vec<(u32, usize)> = Vec::with_capacity(n + 1);
+
+    for (i, q_char) in query.iter().enumerate() {
+        dp_curr.clear();
+        dp_curr.push((i as u32 + 1, 0));
+
+        for j in 1..=n {
+            let (diag_cost, diag_start) = dp_prev[j - 1];
+            let diag = (diag_cost + u32::from(text[j - 1] != *q_char), diag_start);
+            let delete = (dp_prev[j].0 + 1, dp_prev[j].1);
+            let insert = (dp_curr[j - 1].0 + 1, dp_curr[j - 1].1);
+
+            // On equal cost, prefer the diagonal, keeping the window aligned to `query`
+            let best = [diag, delete, insert]
+                .into_iter()
+                .min_by_key(|&(cost, _)| cost)
+                .unwrap_or(diag);
+            dp_curr.push(best);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    let (end, &(dist, start)) = dp_prev
+        .iter()
+        .enumerate()
+        .min_by_key(|&(end, &(cost, _))| (cost, end))
+        .unwrap_or((0, &(0, 0)));
+
+    (start, end, dist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn kitten_in_text() {
+        let text = "the sitting cat";
+        let (start, end, dist) = best_substring_match("kitten", text, true);
+        assert_eq!((start, end, dist), (4, 10, 2));
+        assert_eq!(&text[start..end], "sittin");
+        assert_eq!(edit_distance("kitten", &text[start..end], true), dist);
+    }
+
+    #[test]
+    fn exact_occurrence() {
+        let text = "ʿAlī ibn Abī Ṭālib";
+        assert_eq!(best_substring_match("Abī", text, false), (9, 12, 0));
+        // "Ab" is as close as "Abī", and ends earlier
+        assert_eq!(best_substring_match("Abi", text, false), (9, 11, 1));
+    }
+
+    #[test]
+    fn equally_close_windows() {
+        // "abd" and "abcxd" are both one edit from "abcd"; the first ends earlier
+        let text = "abd zabcxd";
+        assert_eq!(best_substring_match("abcd", text, true), (0, 3, 1));
+        assert_eq!(edit_distance("abcd", &text[5..], true), 1);
+
+        // And the other way around
+        let text = "abcxd zabd";
+        let (start, end, dist) = best_substring_match("abcd", text, true);
+        assert_eq!((end, dist), (3, 1));
+        assert_eq!(edit_distance("abcd", &text[start..end], true), 1);
+    }
+
+    #[test]
+    fn empty_inputs() {
+        assert_eq!(best_substring_match("", "anything", true), (0, 0, 0));
+        assert_eq!(best_substring_match("kitten", "", false), (0, 0, 6));
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+const ONES: u64 = 0x0101_0101_0101_0101;
+const LOW_SEVENS: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+/// Returns the Levenshtein distance between two byte slices, `a` and `b`, using Myers'
+/// bit-parallel algorithm over as many 64-bit blocks as `a` needs
+///
+/// For each byte of `b`, the positions at which it occurs in `a` are found eight bytes at
+/// a time (SIMD within a register) and gathered into a bitmask; each block of the mask
+/// then advances 64 cells of the DP column at once, carrying the horizontal delta from
+/// one block into the next. `a` should be the shorter of the two, and not empty.
+pub fn blocked_distance(a: &[u8], b: &[u8]) -> u32 {
+    let m = a.len();
+    let blocks = m.div_ceil(64);
+
+    // Vertical deltas (positive and negative) of the current column, per block
+    let mut pv: Vec<u64> = vec![!0; blocks];
+    let mut mv: Vec<u64> = vec![0; blocks];
+    let mut eq: Vec<u64> = vec![0; blocks];
+
+    let mut score = m as u32;
+    let last = 1 << ((m - 1) % 64);
+
+    for &b_byte in b {
+        match_mask(a, b_byte, &mut eq);
+
+        // The top row increases by one with each step through `b`
+        let mut h_in: i32 = 1;
+
+        for (k, ((pv, mv), &eq)) in pv.iter_mut().zip(&mut mv).zip(&eq).enumerate() {
+            let neg_in = u64::from(h_in < 0);
+            let xv = eq | *mv;
+            let eq = eq | neg_in;
+            let xh = ((eq & *pv).wrapping_add(*pv) ^ *pv) | eq;
+
+            // Horizontal deltas
+            let mut ph = *mv | !(xh | *pv);
+            let mut mh = *pv & xh;
+
+            // What leaves the bottom of this block enters the top of the next
+            let high = if k == blocks - 1 { last } else { 1 << 63 };
+            let pos_in = u64::from(h_in > 0);
+            h_in = if ph & high != 0 {
+                1
+            } else if mh & high != 0 {
+                -1
+            } else {
+                0
+            };
+
+            ph = (ph << 1) | pos_in;
+            mh = (mh << 1) | neg_in;
+
+            *pv = mh | !(xv | ph);
+            *mv = ph & xv;
+        }
+
+        score = score.wrapping_add_signed(h_in);
+    }
+
+    score
+}
+
+/// Sets bit `j % 64` of `mask[j / 64]` exactly where `a[j] == byte`
+fn match_mask(a: &[u8], byte: u8, mask: &mut [u64]) {
+    let splat = ONES * u64::from(byte);
+
+    for (word, block) in mask.iter_mut().zip(a.chunks(64)) {
+        let mut bits = 0;
+
+        let chunks = block.chunks_exact(8);
+        let tail = chunks.remainder();
+
+        for (k, chunk) in chunks.enumerate() {
+            let chunk: [u8; 8] = chunk.try_into().unwrap_or_default();
+            bits |= zero_bytes(u64::from_le_bytes(chunk) ^ splat) << (k * 8);
+        }
+
+        let offset = block.len() - tail.len();
+        for (k, &x) in tail.iter().enumerate() {
+            bits |= u64::from(x == byte) << (offset + k);
+        }
+
+        *word = bits;
+    }
+}
+
+/// Bit `k` of the result is set if and only if byte `k` (little-endian) of `x` is zero
+const fn zero_bytes(x: u64) -> u64 {
+    // The high bit of each byte ends up set only where the byte was zero: adding 0x7F to
+    // the low seven bits carries into the high bit unless they're all clear, and the
+    // high bit itself is ORed back in. (Unlike the shorter `(x - ONES) & !x` trick, this
+    // has no false positives from borrows.)
+    let high = !(((x & LOW_SEVENS) + LOW_SEVENS) | x | LOW_SEVENS);
+
+    // Gather the eight high bits into the low byte, byte `k` going to bit `k`
+    (high >> 7).wrapping_mul(0x0102_0408_1020_4080) >> 56
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::min_distance;
+    use crate::test_util::Rng;
+
+    #[test]
+    fn zero_byte_detection() {
+        assert_eq!(zero_bytes(0), 0xFF);
+        assert_eq!(zero_bytes(!0), 0);
+        assert_eq!(zero_bytes(0x0100_FF00_8000_0001), 0b0101_0110);
+        // A zero byte just above a one, where borrowing tricks go wrong
+        assert_eq!(zero_bytes(0x0000_0000_0000_0100), 0b1111_1101);
+    }
+
+    #[test]
+    fn masks() {
+        let a = b"abracadabra, abracadabra, abracadabra";
+        let mut mask = [0; 1];
+        match_mask(a, b'a', &mut mask);
+
+        for (j, &x) in a.iter().enumerate() {
+            assert_eq!(mask[0] >> j & 1 == 1, x == b'a');
+        }
+        assert_eq!(mask[0] >> a.len(), 0);
+    }
+
+    #[test]
+    fn random_against_scalar() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+        for alphabet in [
+            &b"ACGT"[..],
+            b"ab",
+            b"abcdefghijklmnopqrstuvwxyz",
+            &[0, 1, 0xFF],
+        ] {
+            for _ in 0..300 {
+                let a = rng.bytes(300, alphabet);
+                let b = rng.bytes(300, alphabet);
+                assert_eq!(blocked_distance(&a, &b), min_distance(&a, &b));
+            }
+        }
+
+        assert_eq!(blocked_distance(b"kitten", b"sitting"), 3);
+        assert_eq!(blocked_distance(b"sitting", b""), 7);
+    }
+}
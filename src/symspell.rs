@@ -0,0 +1,165 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::edit_distance;
+
+/// An index of words for fast spelling correction, using symmetric deletes (as in the
+/// `SymSpell` algorithm)
+///
+/// ```
+/// use agnostic_levenshtein::SymSpellIndex;
+///
+/// let index = SymSpellIndex::build(&["kitten", "sitting", "mitten"], 2);
+/// assert_eq!(
+///     index.lookup("kiten", 2),
+///     [("kitten".to_string(), 1), ("mitten".to_string(), 2)]
+/// );
+/// ```
+///
+/// Every way of deleting up to `max_edit` characters from each word is stored in
+/// advance. Any word within `max_edit` edits of a query then shares at least one of
+/// these variants with it, so a lookup only needs to generate the deletes of the query
+/// and verify the words they lead to. Strings are compared as sequences of `char`s.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SymSpellIndex {
+    max_edit: u32,
+    words: Vec<String>,
+    // Each delete variant, with the indices of the words it came from
+    deletes: BTreeMap<String, Vec<usize>>,
+}
+
+impl SymSpellIndex {
+    /// Builds an index of `words` supporting lookups of up to `max_edit` edits
+    ///
+    /// The index grows quickly with `max_edit` and the length of the words: a word of
+    /// `n` characters has on the order of `n^max_edit` delete variants.
+    #[must_use]
+    pub fn build(words: &[&str], max_edit: u32) -> Self {
+        let words: Vec<String> = words
+            .iter()
+            .copied()
+            .collect::<BTreeSet<&str>>()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+
+        let mut deletes: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (idx, word) in words.iter().enumerate() {
+            for variant in delete_variants(word, max_edit) {
+                deletes.entry(variant).or_default().push(idx);
+            }
+        }
+
+        Self {
+            max_edit,
+            words,
+            deletes,
+        }
+    }
+
+    /// Returns the indexed words within `max_edit` of `term`, along with their
+    /// Levenshtein distances, sorted by distance and then alphabetically
+    ///
+    /// `max_edit` is capped at the value the index was built with.
+    #[must_use]
+    pub fn lookup(&self, term: &str, max_edit: u32) -> Vec<(String, u32)> {
+        let max_edit = max_edit.min(self.max_edit);
+
+        let candidates: BTreeSet<usize> = delete_variants(term, max_edit)
+            .iter()
+            .filter_map(|variant| self.deletes.get(variant))
+            .flatten()
+            .copied()
+            .collect();
+
+        let mut found: Vec<(String, u32)> = candidates
+            .into_iter()
+            .filter_map(|idx| {
+                let word = &self.words[idx];
+                let dist = edit_distance(word, term, false);
+                (dist <= max_edit).then(|| (word.clone(), dist))
+            })
+            .collect();
+
+        found.sort_by(|(x, x_dist), (y, y_dist)| x_dist.cmp(y_dist).then_with(|| x.cmp(y)));
+        found
+    }
+}
+
+/// All strings obtained by deleting up to `max_edit` `char`s from `s`, including `s`
+fn delete_variants(s: &str, max_edit: u32) -> BTreeSet<String> {
+    let mut variants: BTreeSet<String> = BTreeSet::new();
+    variants.insert(s.to_string());
+
+    let mut frontier: Vec<String> = Vec::from([s.to_string()]);
+    for _ in 0..max_edit {
+        let mut next: Vec<String> = Vec::new();
+
+        for word in &frontier {
+            for (i, c) in word.char_indices() {
+                let mut variant = String::with_capacity(word.len());
+                variant.push_str(&word[..i]);
+                variant.push_str(&word[i + c.len_utf8()..]);
+
+                if variants.insert(variant.clone()) {
+                    next.push(variant);
+                }
+            }
+        }
+
+        frontier = next;
+    }
+
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 8] = [
+        "kitten",
+        "sitting",
+        "mitten",
+        "bitten",
+        "kitchen",
+        "written",
+        "maḥmūd",
+        "muḥammad",
+    ];
+
+    #[test]
+    fn misspelling_within_two() {
+        let index = SymSpellIndex::build(&WORDS, 2);
+        let found = index.lookup("kittin", 2);
+
+        let mut expected: Vec<(String, u32)> = WORDS
+            .iter()
+            .map(|&w| (w.to_string(), edit_distance(w, "kittin", false)))
+            .filter(|&(_, dist)| dist <= 2)
+            .collect();
+        expected.sort_by(|(x, x_dist), (y, y_dist)| x_dist.cmp(y_dist).then_with(|| x.cmp(y)));
+
+        assert_eq!(found, expected);
+        assert_eq!(found[0], ("kitten".to_string(), 1));
+    }
+
+    #[test]
+    fn unicode_and_cap() {
+        let index = SymSpellIndex::build(&WORDS, 1);
+        assert_eq!(index.lookup("mahmūd", 1), [("maḥmūd".to_string(), 1)]);
+        // Can't look further than the index was built for
+        assert!(index.lookup("mahmud", 2).is_empty());
+    }
+
+    #[test]
+    fn deletes() {
+        let variants = delete_variants("abc", 1);
+        assert_eq!(
+            variants.into_iter().collect::<Vec<_>>(),
+            ["ab", "abc", "ac", "bc"]
+        );
+        assert_eq!(delete_variants("abc", 5).len(), 8);
+    }
+}
@@ -0,0 +1,23 @@
+//! Helpers shared by the unit tests
+
+use alloc::vec::Vec;
+
+/// A small xorshift generator, so that the tests are reproducible
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Between 1 and `max_len` bytes drawn from `alphabet`
+    pub fn bytes(&mut self, max_len: u64, alphabet: &[u8]) -> Vec<u8> {
+        let len = 1 + self.next() % max_len;
+        (0..len)
+            .map(|_| alphabet[(self.next() % alphabet.len() as u64) as usize])
+            .collect()
+    }
+}
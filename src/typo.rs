@@ -0,0 +1,201 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+
+use crate::EditOp;
+
+/// Where an edit falls in the intended word, for `TypoModel::edit_cost`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Context {
+    /// The index in the intended word of the character deleted or substituted, or of the
+    /// one before which a character is inserted (counting `char`s)
+    pub position: usize,
+    /// The character of the intended word before that position, if any
+    pub prev: Option<char>,
+    /// The character of the intended word after the one deleted or substituted, or at the
+    /// insertion point, if any
+    pub next: Option<char>,
+}
+
+/// The costs of typing errors, for ranking suggestions with `weighted_suggest`
+///
+/// Edits transform the intended word (a suggestion) into what was typed, so, e.g.,
+/// `EditOp::Delete('e')` means that an "e" was left out. Costs would typically be
+/// negative log probabilities, so that the cheapest suggestion is the likeliest, and
+/// should not be negative.
+pub trait TypoModel {
+    /// The cost of the edit `op` at `context`; never called for `EditOp::Match`, which
+    /// is free
+    fn edit_cost(&self, op: &EditOp, context: &Context) -> f64;
+}
+
+/// The typo model in which every edit costs 1.0, so that costs are Levenshtein distances
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UniformModel;
+
+impl TypoModel for UniformModel {
+    fn edit_cost(&self, _op: &EditOp, _context: &Context) -> f64 {
+        1.0
+    }
+}
+
+/// Returns `options` ranked as corrections of `input` according to `model`, each with
+/// its cost, from the cheapest
+///
+/// ```
+/// use agnostic_levenshtein::{UniformModel, weighted_suggest};
+///
+/// let ranked = weighted_suggest("kiten", &["sitting", "kitten"], &UniformModel);
+/// assert_eq!(ranked, [("kitten", 1.0), ("sitting", 4.0)]);
+/// ```
+///
+/// The cost of an option is that of the cheapest sequence of edits turning it into
+/// `input`. Ties keep the order of `options`. Strings are compared as sequences of
+/// `char`s.
+#[must_use]
+pub fn weighted_suggest<'a, M: TypoModel + ?Sized>(
+    input: &str,
+    options: &'a [&'a str],
+    model: &M,
+) -> Vec<(&'a str, f64)> {
+    let typed: Vec<char> = input.chars().collect();
+    let mut intended: Vec<char> = Vec::new();
+
+    let mut ranked: Vec<(&str, f64)> = options
+        .iter()
+        .map(|&option| {
+            intended.clear();
+            intended.extend(option.chars());
+            (option, typo_cost(&intended, &typed, model))
+        })
+        .collect();
+
+    ranked.sort_by(|x, y| x.1.total_cmp(&y.1));
+    ranked
+}
+
+/// The cheapest cost under `model` of edits turning `intended` into `typed`
+fn typo_cost<M: TypoModel + ?Sized>(intended: &[char], typed: &[char], model: &M) -> f64 {
+    let context = |position: usize, next: usize| Context {
+        position,
+        prev: position.checked_sub(1).map(|k| intended[k]),
+        next: intended.get(next).copied(),
+    };
+    let delete = |i: usize| model.edit_cost(&EditOp::Delete(intended[i]), &context(i, i + 1));
+    let insert = |i: usize, c: char| model.edit_cost(&EditOp::Insert(c), &context(i, i));
+
+    let n = typed.len();
+
+    // Typing a prefix of `typed` with nothing intended
+    let mut dp_prev: Vec<f64> = Vec::with_capacity(n + 1);
+    dp_prev.push(0.0);
+    for (j, &c) in typed.iter().enumerate() {
+        dp_prev.push(dp_prev[j] + insert(0, c));
+    }
+    let mut dp_curr: Vec<f64> = vec![0.0; n + 1];
+
+    for (i, &x) in intended.iter().enumerate() {
+        dp_curr[0] = dp_prev[0] + delete(i);
+
+        for (j, &y) in typed.iter().enumerate() {
+            let substitute = if x == y {
+                dp_prev[j]
+            } else {
+                let op = EditOp::Substitute { from: x, to: y };
+                dp_prev[j] + model.edit_cost(&op, &context(i, i + 1))
+            };
+            let deleted = dp_prev[j + 1] + delete(i);
+            let inserted = dp_curr[j] + insert(i + 1, y);
+
+            dp_curr[j + 1] = substitute.min(deleted).min(inserted);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    /// Leaving out an "e" after an "r" is a common slip
+    struct DroppedE;
+
+    impl TypoModel for DroppedE {
+        fn edit_cost(&self, op: &EditOp, context: &Context) -> f64 {
+            match (op, context.prev) {
+                (EditOp::Delete('e'), Some('r')) => 0.1,
+                _ => 1.0,
+            }
+        }
+    }
+
+    #[test]
+    fn cheap_deletion_changes_ranking() {
+        let options = ["then", "there"];
+        assert_eq!(
+            weighted_suggest("ther", &options, &UniformModel),
+            [("then", 1.0), ("there", 1.0)]
+        );
+
+        let ranked = weighted_suggest("ther", &options, &DroppedE);
+        assert_eq!(ranked[0].0, "there");
+        assert!((ranked[0].1 - 0.1).abs() < 1e-9);
+        assert_eq!(ranked[1], ("then", 1.0));
+
+        // Not after another letter
+        let ranked = weighted_suggest("thn", &["then", "thin"], &DroppedE);
+        assert_eq!(ranked, [("then", 1.0), ("thin", 1.0)]);
+    }
+
+    #[test]
+    fn contexts() {
+        /// Records the context of every deletion considered
+        struct Recorder(core::cell::RefCell<Vec<(char, Context)>>);
+
+        impl TypoModel for Recorder {
+            fn edit_cost(&self, op: &EditOp, context: &Context) -> f64 {
+                if let EditOp::Delete(c) = op {
+                    self.0.borrow_mut().push((*c, *context));
+                }
+                1.0
+            }
+        }
+
+        let recorder = Recorder(core::cell::RefCell::new(Vec::new()));
+        let _ = weighted_suggest("", &["ʿAlī"], &recorder);
+        let deletions = recorder.0.into_inner();
+        assert_eq!(deletions.len(), 4);
+        assert_eq!(
+            deletions[1],
+            (
+                'A',
+                Context {
+                    position: 1,
+                    prev: Some('ʿ'),
+                    next: Some('l')
+                }
+            )
+        );
+        assert_eq!(deletions[3].1.next, None);
+    }
+
+    #[test]
+    fn uniform_is_levenshtein() {
+        let options = [
+            "kitten",
+            "sitting",
+            "",
+            "ʿAlī ibn Abī Ṭālib",
+            "ʿUthmān ibn ʿAffān",
+        ];
+        for input in options {
+            for (option, cost) in weighted_suggest(input, &options, &UniformModel) {
+                assert!((cost - f64::from(edit_distance(option, input, false))).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! WebAssembly bindings for the core function, enabled by the `wasm` feature
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::edit_distance;
+
+/// Returns the Levenshtein distance between two strings, `a` and `b`, for callers in
+/// JavaScript. The `ascii` flag works as for `edit_distance`.
+///
+/// JavaScript strings are UTF-16, and `wasm-bindgen` converts them to UTF-8 on the way
+/// in. With `ascii` set to false, the distance is then counted in Unicode scalar values,
+/// so a character outside the Basic Multilingual Plane (e.g., most emoji) counts as one
+/// unit, even though it has a `length` of 2 in JavaScript. The function is exported with
+/// `wasm-bindgen` only when compiling for `wasm32`; elsewhere, it's a plain function.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[must_use]
+pub fn edit_distance_wasm(a: &str, b: &str, ascii: bool) -> u32 {
+    edit_distance(a, b, ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_edit_distance() {
+        assert_eq!(edit_distance_wasm("kitten", "sitting", true), 3);
+        // One scalar value, though two UTF-16 code units and four bytes
+        assert_eq!(edit_distance_wasm("\u{1F600}", "", false), 1);
+        assert_eq!(edit_distance_wasm("\u{1F600}", "", true), 4);
+    }
+}
@@ -0,0 +1,382 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::swap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Costs of the individual edit operations, for use with `weighted_distance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Weights {
+    /// Cost of inserting a character from `b`
+    pub insert: u32,
+    /// Cost of deleting a character from `a`
+    pub delete: u32,
+    /// Cost of replacing a character from `a` with one from `b`
+    pub substitute: u32,
+}
+
+impl Default for Weights {
+    /// All operations cost 1, as in `edit_distance`
+    fn default() -> Self {
+        Self {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+        }
+    }
+}
+
+/// Returns the weighted edit distance (`u32`) between two strings, `a` and `b`, i.e.,
+/// the minimum total cost of edits transforming `a` into `b`. The `ascii` flag works as
+/// for `edit_distance`.
+#[must_use]
+pub fn weighted_distance(a: &str, b: &str, ascii: bool, weights: &Weights) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    if ascii {
+        weighted_min_distance(a.as_bytes(), b.as_bytes(), weights)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        weighted_min_distance(&a_chars, &b_chars, weights)
+    }
+}
+
+pub fn weighted_min_distance<T: PartialEq>(a: &[T], b: &[T], weights: &Weights) -> u32 {
+    let m = a.len();
+
+    // Base case: the cost of deleting each prefix of `a`. Costs saturate at `u32::MAX`,
+    // since the weights may be arbitrarily large.
+    let mut dp_prev: Vec<u32> = (0..=m as u32)
+        .map(|j| j.saturating_mul(weights.delete))
+        .collect();
+    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+
+    for (i, b_char) in b.iter().enumerate() {
+        // i.e., cost of inserting all chars from `b` up to this point
+        dp_curr[0] = (i as u32 + 1).saturating_mul(weights.insert);
+
+        for j in 1..=m {
+            let sub_cost = if a[j - 1] == *b_char {
+                0
+            } else {
+                weights.substitute
+            };
+
+            let insert = dp_prev[j].saturating_add(weights.insert);
+            let delete = dp_curr[j - 1].saturating_add(weights.delete);
+            let substitute = dp_prev[j - 1].saturating_add(sub_cost);
+
+            dp_curr[j] = insert.min(delete).min(substitute);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+/// Returns the edit distance (`u32`) between two strings, `a` and `b`, where the cost of
+/// each substitution is given by `sub_cost`. Strings are compared as sequences of `char`s.
+///
+/// `sub_cost(x, y)` is the cost of replacing `x` from `a` with `y` from `b`; it is called
+/// only for distinct characters, since a match is always free. Insertions and deletions
+/// cost 1. This allows, e.g., visually similar glyphs to be cheaper to swap.
+#[must_use]
+pub fn edit_distance_with<F: Fn(char, char) -> u32>(a: &str, b: &str, sub_cost: F) -> u32 {
+    if a == b {
+        return 0;
+    }
+
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    custom_min_distance(&a_chars, &b_chars, sub_cost)
+}
+
+fn custom_min_distance<T: PartialEq + Copy>(
+    a: &[T],
+    b: &[T],
+    sub_cost: impl Fn(T, T) -> u32,
+) -> u32 {
+    let m = a.len();
+
+    let mut dp_prev: Vec<u32> = (0..=m as u32).collect();
+    let mut dp_curr: Vec<u32> = vec![0; m + 1];
+
+    for (i, &b_char) in b.iter().enumerate() {
+        dp_curr[0] = i as u32 + 1;
+
+        for j in 1..=m {
+            let a_char = a[j - 1];
+            let cost = if a_char == b_char {
+                0
+            } else {
+                sub_cost(a_char, b_char)
+            };
+
+            let insert = dp_prev[j] + 1;
+            let delete = dp_curr[j - 1] + 1;
+            let substitute = dp_prev[j - 1] + cost;
+
+            dp_curr[j] = insert.min(delete).min(substitute);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+/// Returns a score for the difference between two strings, `a` and `b`, in which edits
+/// near the start cost more than those near the end. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// Each edit costs `decay.powi(position)`, where its position is how far into both
+/// strings it occurs: the lesser of its indices in `a` and `b`. With a `decay` below 1.0,
+/// a typo in the first character outweighs one in the last, as suits, e.g., ranking
+/// autocomplete suggestions; with a `decay` of 1.0, the score is the Levenshtein
+/// distance.
+///
+/// This is a heuristic, not a metric: the triangle inequality need not hold, since the
+/// cost of an edit depends on where an alignment puts it.
+#[must_use]
+pub fn prefix_weighted_distance(a: &str, b: &str, decay: f64, ascii: bool) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+
+    if ascii {
+        prefix_weighted_min_distance(a.as_bytes(), b.as_bytes(), decay)
+    } else {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        prefix_weighted_min_distance(&a_chars, &b_chars, decay)
+    }
+}
+
+fn prefix_weighted_min_distance<T: PartialEq>(a: &[T], b: &[T], decay: f64) -> f64 {
+    let m = a.len();
+
+    // The cost of an edit at each position
+    let mut costs: Vec<f64> = Vec::with_capacity(m.max(b.len()) + 1);
+    let mut cost = 1.0;
+    for _ in 0..=m.max(b.len()) {
+        costs.push(cost);
+        cost *= decay;
+    }
+
+    // Deleting a prefix of `a` from the empty string happens at position 0
+    let mut dp_prev: Vec<f64> = (0..=m as u32).map(f64::from).collect();
+    let mut dp_curr: Vec<f64> = vec![0.0; m + 1];
+
+    for (i, b_char) in b.iter().enumerate() {
+        dp_curr[0] = dp_prev[0] + costs[0];
+
+        for j in 1..=m {
+            let sub_cost = if a[j - 1] == *b_char {
+                0.0
+            } else {
+                costs[i.min(j - 1)]
+            };
+
+            let insert = dp_prev[j] + costs[i.min(j)];
+            let delete = dp_curr[j - 1] + costs[(i + 1).min(j - 1)];
+            let substitute = dp_prev[j - 1] + sub_cost;
+
+            dp_curr[j] = insert.min(delete).min(substitute);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[m]
+}
+
+/// Returns a score for the difference between two strings, `a` and `b`, in which edits
+/// to rare characters cost more than edits to common ones
+///
+/// Inserting or deleting a character `c` costs `1.0 / freq[c]`, and a substitution costs
+/// as much as the rarer of the two characters. A character missing from `freq`, or with
+/// a frequency that isn't positive, is taken to be as rare as the rarest one in it (or
+/// to cost 1.0, if there is none). Strings are compared as sequences of `char`s.
+/// Requires the `std` feature.
+///
+/// Frequencies can be on any scale, e.g., counts from a corpus or their proportions; only
+/// their relative sizes affect which alignment is cheapest. Like
+/// `prefix_weighted_distance`, this is a heuristic for ranking, not a metric.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn idf_weighted_distance<S: core::hash::BuildHasher>(
+    a: &str,
+    b: &str,
+    freq: &HashMap<char, f64, S>,
+) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+
+    let rarest = freq
+        .values()
+        .copied()
+        .filter(|&f| f > 0.0)
+        .min_by(f64::total_cmp)
+        .unwrap_or(1.0);
+    let weight = |c: char| {
+        let f = freq.get(&c).copied().filter(|&f| f > 0.0).unwrap_or(rarest);
+        1.0 / f
+    };
+
+    let a: Vec<(char, f64)> = a.chars().map(|c| (c, weight(c))).collect();
+    let b: Vec<(char, f64)> = b.chars().map(|c| (c, weight(c))).collect();
+
+    // The cost of deleting each prefix of `a`
+    let mut dp_prev: Vec<f64> = Vec::with_capacity(a.len() + 1);
+    dp_prev.push(0.0);
+    for &(_, w) in &a {
+        dp_prev.push(dp_prev[dp_prev.len() - 1] + w);
+    }
+    let mut dp_curr: Vec<f64> = vec![0.0; a.len() + 1];
+
+    for &(y, y_weight) in &b {
+        dp_curr[0] = dp_prev[0] + y_weight;
+
+        for (j, &(x, x_weight)) in a.iter().enumerate() {
+            let sub_cost = if x == y { 0.0 } else { x_weight.max(y_weight) };
+
+            let insert = dp_prev[j + 1] + y_weight;
+            let delete = dp_curr[j] + x_weight;
+            let substitute = dp_prev[j] + sub_cost;
+
+            dp_curr[j + 1] = insert.min(delete).min(substitute);
+        }
+
+        swap(&mut dp_prev, &mut dp_curr);
+    }
+
+    dp_prev[a.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn default_matches_unweighted() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        let weights = Weights::default();
+        assert_eq!(
+            weighted_distance(a, b, false, &weights),
+            edit_distance(a, b, false)
+        );
+        assert_eq!(
+            weighted_distance(a, b, true, &weights),
+            edit_distance(a, b, true)
+        );
+    }
+
+    #[test]
+    fn expensive_substitution() {
+        let weights = Weights {
+            substitute: 2,
+            ..Weights::default()
+        };
+        assert_eq!(weighted_distance("abc", "abd", true, &weights), 2);
+    }
+
+    #[test]
+    fn asymmetric_indels() {
+        let weights = Weights {
+            insert: 1,
+            delete: 5,
+            substitute: 3,
+        };
+        assert_eq!(weighted_distance("", "abc", true, &weights), 3);
+        assert_eq!(weighted_distance("abc", "", true, &weights), 15);
+        assert_eq!(weighted_distance("abcd", "abc", false, &weights), 5);
+        // Cheaper to substitute than to delete and insert
+        assert_eq!(weighted_distance("abc", "xbc", false, &weights), 3);
+    }
+
+    #[test]
+    fn huge_weights_saturate() {
+        let weights = Weights {
+            insert: u32::MAX,
+            delete: u32::MAX / 2,
+            substitute: 1,
+        };
+        assert_eq!(weighted_distance("", "abc", true, &weights), u32::MAX);
+        assert_eq!(weighted_distance("abc", "", false, &weights), u32::MAX);
+        assert_eq!(weighted_distance("abc", "abxd", true, &weights), u32::MAX);
+        // Still exact below the limit
+        assert_eq!(weighted_distance("kitten", "sitten", true, &weights), 1);
+        assert_eq!(weighted_distance("ab", "", true, &weights), u32::MAX - 1);
+    }
+
+    #[test]
+    fn custom_vowels_free() {
+        let is_vowel = |c: char| "aeiouāīū".contains(c);
+        let sub_cost = |x: char, y: char| u32::from(!(is_vowel(x) && is_vowel(y)));
+
+        assert_eq!(edit_distance_with("Firdawsi", "Ferdowsi", sub_cost), 0);
+        assert_eq!(edit_distance_with("kitten", "sitting", sub_cost), 2);
+        assert_eq!(edit_distance_with("maḥmūd", "muḥammad", sub_cost), 2);
+    }
+
+    #[test]
+    fn custom_unit_cost_matches_unweighted() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        assert_eq!(
+            edit_distance_with(a, b, |_, _| 1),
+            edit_distance(a, b, false)
+        );
+    }
+
+    #[test]
+    fn early_edit_costs_more() {
+        let early = prefix_weighted_distance("kitten", "mitten", 0.5, true);
+        let late = prefix_weighted_distance("kitten", "kittem", 0.5, true);
+        assert!((early - 1.0).abs() < 1e-9);
+        assert!((late - 0.5_f64.powi(5)).abs() < 1e-9);
+
+        // Appending to a prefix, as when completing what's been typed
+        let completed = prefix_weighted_distance("kit", "kitten", 0.5, false);
+        assert!((completed - (0.125 + 0.125 + 0.125)).abs() < 1e-9);
+        assert!(prefix_weighted_distance("maḥmūd", "maḥmūd", 0.5, false) == 0.0);
+    }
+
+    #[test]
+    fn no_decay_matches_unweighted() {
+        let a = "ʿAlī ibn Abī Ṭālib";
+        let b = "ʿUthmān ibn ʿAffān";
+        for ascii in [true, false] {
+            let score = prefix_weighted_distance(a, b, 1.0, ascii);
+            assert!((score - f64::from(edit_distance(a, b, ascii))).abs() < 1e-9);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn idf_rare_costs_more() {
+        let freq: HashMap<char, f64> = [('e', 12.0), ('t', 9.0), ('a', 8.0), ('q', 0.1)]
+            .into_iter()
+            .collect();
+
+        let common = idf_weighted_distance("tea", "ta", &freq);
+        let rare = idf_weighted_distance("tqa", "ta", &freq);
+        assert!((common - 1.0 / 12.0).abs() < 1e-9);
+        assert!((rare - 10.0).abs() < 1e-9);
+        assert!(rare > common);
+
+        // A substitution costs as much as the rarer character
+        assert!((idf_weighted_distance("tea", "tqa", &freq) - 10.0).abs() < 1e-9);
+        // Unseen characters are as rare as the rarest
+        assert!((idf_weighted_distance("ta", "tza", &freq) - 10.0).abs() < 1e-9);
+        assert!(idf_weighted_distance("", "", &freq).abs() < f64::EPSILON);
+        assert!((idf_weighted_distance("kitten", "sitting", &HashMap::new()) - 3.0).abs() < 1e-9);
+    }
+}
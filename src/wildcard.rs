@@ -0,0 +1,83 @@
+use alloc::vec::Vec;
+
+use crate::comparable::{EditComparable, edit_distance_by};
+
+/// A unit of the pattern or the text; only one from the pattern can be a wildcard
+struct Unit<T> {
+    value: T,
+    wildcard: bool,
+}
+
+impl<T: PartialEq> EditComparable for Unit<T> {
+    fn edit_eq(&self, other: &Self) -> bool {
+        self.wildcard || other.wildcard || self.value == other.value
+    }
+}
+
+/// Returns the Levenshtein distance (`u32`) between a `pattern` and a `text`, where `?`
+/// in the pattern matches any one character at no cost. The `ascii` flag works as for
+/// `edit_distance`.
+///
+/// A `?` can still be inserted into or deleted from the pattern, at the usual cost, so
+/// "c?t" is one edit from "ct" and from "cast". There is no way to escape a `?`, and one
+/// in the text is an ordinary character.
+#[must_use]
+pub fn edit_distance_wildcard(pattern: &str, text: &str, ascii: bool) -> u32 {
+    if ascii {
+        distance(pattern.bytes(), text.bytes(), b'?')
+    } else {
+        distance(pattern.chars(), text.chars(), '?')
+    }
+}
+
+fn distance<T: PartialEq + Copy>(
+    pattern: impl Iterator<Item = T>,
+    text: impl Iterator<Item = T>,
+    wildcard: T,
+) -> u32 {
+    let pattern: Vec<Unit<T>> = pattern
+        .map(|value| Unit {
+            value,
+            wildcard: value == wildcard,
+        })
+        .collect();
+    let text: Vec<Unit<T>> = text
+        .map(|value| Unit {
+            value,
+            wildcard: false,
+        })
+        .collect();
+
+    edit_distance_by(&pattern, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_distance;
+
+    #[test]
+    fn wildcards() {
+        for ascii in [true, false] {
+            assert_eq!(edit_distance_wildcard("c?t", "cat", ascii), 0);
+            assert_eq!(edit_distance_wildcard("c?t", "cast", ascii), 1);
+            assert_eq!(edit_distance_wildcard("c?t", "ct", ascii), 1);
+            assert_eq!(edit_distance_wildcard("???", "dog", ascii), 0);
+            assert_eq!(edit_distance_wildcard("kitten", "sitting", ascii), 3);
+        }
+
+        // Only in the pattern
+        assert_eq!(edit_distance_wildcard("cat", "c?t", false), 1);
+        assert_eq!(edit_distance_wildcard("Ṭ?lib", "Ṭālib", false), 0);
+        assert_eq!(edit_distance_wildcard("m?ḥmūd", "maḥmud", false), 1);
+    }
+
+    #[test]
+    fn no_wildcards() {
+        let (a, b) = ("ʿAlī ibn Abī Ṭālib", "ʿUthmān ibn ʿAffān");
+        assert_eq!(
+            edit_distance_wildcard(a, b, false),
+            edit_distance(a, b, false)
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::vec::Vec;
+
+use crate::edit_distance_slice;
+
+/// Returns the word-level Levenshtein distance between two strings, `a` and `b`, i.e.,
+/// the minimum number of whole-word insertions, deletions, or substitutions.
+///
+/// Both strings are split on whitespace, and each word is compared as an atomic unit: a
+/// word differing by a single character counts as one substitution, not several edits.
+#[must_use]
+pub fn word_distance(a: &str, b: &str) -> u32 {
+    let a_words: Vec<&str> = a.split_whitespace().collect();
+    let b_words: Vec<&str> = b.split_whitespace().collect();
+    edit_distance_slice(&a_words, &b_words)
+}
+
+/// Returns the word-level Levenshtein distance between two strings, `a` and `b`, where
+/// words that are anagrams of each other count as the same
+///
+/// As in `word_distance`, the strings are split on whitespace and words are inserted,
+/// deleted, or substituted whole, so word order matters; but the order of letters within
+/// a word doesn't, so transposed letters (as in "recieve" and "receive") cost nothing.
+/// Letters are compared as `char`s, and each word is compared by its sorted multiset of
+/// them.
+#[must_use]
+pub fn word_anagram_distance(a: &str, b: &str) -> u32 {
+    let sorted_words = |s: &str| -> Vec<Vec<char>> {
+        s.split_whitespace()
+            .map(|word| {
+                let mut chars: Vec<char> = word.chars().collect();
+                chars.sort_unstable();
+                chars
+            })
+            .collect()
+    };
+
+    edit_distance_slice(&sorted_words(a), &sorted_words(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_word_substituted() {
+        assert_eq!(word_distance("the quick fox", "the slow fox"), 1);
+    }
+
+    #[test]
+    fn words_inserted_and_deleted() {
+        let a = "Abū al-Qāsim Firdawsī";
+        let b = "Abū al-Qāsim Manṣūr ibn Ḥasan Firdawsī Ṭūsī";
+        assert_eq!(word_distance(a, b), 4);
+        assert_eq!(word_distance(b, a), 4);
+    }
+
+    #[test]
+    fn empty_side() {
+        assert_eq!(word_distance("", "the quick fox"), 3);
+        assert_eq!(word_distance("the quick fox", ""), 3);
+        assert_eq!(word_distance("", "  "), 0);
+    }
+
+    #[test]
+    fn whitespace_ignored() {
+        assert_eq!(word_distance("the  quick\tfox", " the quick fox\n"), 0);
+    }
+
+    #[test]
+    fn anagram_words() {
+        assert_eq!(word_anagram_distance("teh cat", "the cat"), 0);
+        assert_eq!(word_anagram_distance("the dog", "the cat"), 1);
+        assert_eq!(word_anagram_distance("i recieve it", "i receive it"), 0);
+        // Word order still matters
+        assert_eq!(word_anagram_distance("cat teh", "the cat"), 2);
+        assert_eq!(word_anagram_distance("ʿAlī ibn", "ʿAīl ibn Ṭālib"), 1);
+        assert_eq!(word_anagram_distance("", "teh cat"), 2);
+    }
+}
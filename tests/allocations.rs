@@ -0,0 +1,88 @@
+//! Heap allocations made by `edit_distance`, counted by a wrapping global allocator
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use agnostic_levenshtein::edit_distance;
+
+struct Counting;
+
+thread_local! {
+    // Per thread, since tests run in parallel: (allocations, bytes)
+    static ALLOCATED: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.with(|n| {
+            let (count, bytes) = n.get();
+            n.set((count + 1, bytes + layout.size()));
+        });
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// The number and total size of the allocations made by `f`
+fn allocations(f: impl FnOnce() -> u32) -> (usize, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    std::hint::black_box(f());
+    let after = ALLOCATED.with(Cell::get);
+    (after.0 - before.0, after.1 - before.1)
+}
+
+#[test]
+fn unicode_streams_longer_string() {
+    let short = "ʿAlī ibn Abī Ṭālib ".repeat(4);
+    let long = "Abū Manṣūr Muḥammad ibn ʿAbd al-Razzāq ".repeat(10);
+    let longer = long.repeat(10);
+
+    let (count, bytes) = allocations(|| edit_distance(&short, &long, false));
+    // Only the shorter string is collected, and the DP rows are sized by it
+    assert!(count <= 5, "{count} allocations");
+    assert!(bytes < 16 * short.len(), "{bytes} bytes");
+
+    // So a longer second string costs nothing more, in either position
+    assert_eq!(
+        allocations(|| edit_distance(&short, &longer, false)),
+        (count, bytes)
+    );
+    assert_eq!(
+        allocations(|| edit_distance(&longer, &short, false)),
+        (count, bytes)
+    );
+}
+
+#[test]
+fn short_paths_allocate_nothing() {
+    assert_eq!(
+        allocations(|| edit_distance("kitten", "sitting", true)),
+        (0, 0)
+    );
+    assert_eq!(allocations(|| edit_distance("maḥmūd", "", false)), (0, 0));
+    assert_eq!(
+        allocations(|| edit_distance("ʿ", "ʿAlī ibn Abī Ṭālib", false)),
+        (0, 0)
+    );
+    assert_eq!(
+        allocations(|| edit_distance("شاهنامه", "شاهنامه", false)),
+        (0, 0)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn memoized_hits_allocate_nothing() {
+    let mut memo = agnostic_levenshtein::MemoizedDistance::new();
+    let _ = memo.distance("maḥmūd", "muḥammad", false);
+    assert_eq!(
+        allocations(|| memo.distance("muḥammad", "maḥmūd", false)),
+        (0, 0)
+    );
+}
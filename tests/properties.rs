@@ -0,0 +1,98 @@
+//! Metric properties of `edit_distance`, checked over randomly generated strings
+
+use agnostic_levenshtein::edit_distance;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+const ASCII: [char; 4] = ['a', 'b', 'c', 'd'];
+const UNICODE: [char; 6] = ['a', 'ā', 'ḥ', 'ʿ', 'ش', '😀'];
+// Long enough to exercise both the bit-parallel and the general algorithm
+const MAX_LEN: usize = 80;
+
+/// A string of up to `MAX_LEN` characters drawn from `alphabet`
+fn string(alphabet: &'static [char]) -> impl Strategy<Value = String> {
+    prop::collection::vec(select(alphabet), 0..=MAX_LEN).prop_map(String::from_iter)
+}
+
+/// A few random edits, each as (position, kind, character)
+fn edits(alphabet: &'static [char]) -> impl Strategy<Value = Vec<(usize, u8, char)>> {
+    prop::collection::vec((any::<usize>(), 0..3_u8, select(alphabet)), 0..4)
+}
+
+/// A variant of `s` with `edits` applied, so that related strings are common
+fn mutate(s: &str, edits: &[(usize, u8, char)]) -> String {
+    let mut chars: Vec<char> = s.chars().collect();
+
+    for &(idx, kind, c) in edits {
+        let idx = idx % (chars.len() + 1);
+        match kind {
+            0 => chars.insert(idx, c),
+            1 if idx < chars.len() => chars[idx] = c,
+            _ if idx < chars.len() => {
+                chars.remove(idx);
+            }
+            _ => {}
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Triples of strings, each usually a few edits from the last, and a mode; ASCII mode
+/// only sees ASCII
+fn triples() -> impl Strategy<Value = (String, String, String, bool)> {
+    let from = |alphabet: &'static [char]| {
+        (
+            string(alphabet),
+            edits(alphabet),
+            edits(alphabet),
+            prop::option::of(string(alphabet)),
+        )
+            .prop_map(|(a, ab, bc, unrelated)| {
+                let b = mutate(&a, &ab);
+                let c = unrelated.unwrap_or_else(|| mutate(&b, &bc));
+                (a, b, c)
+            })
+    };
+
+    prop_oneof![(from(&ASCII), any::<bool>()), (from(&UNICODE), Just(false))]
+        .prop_map(|((a, b, c), ascii)| (a, b, c, ascii))
+}
+
+fn len(s: &str, ascii: bool) -> u32 {
+    (if ascii { s.len() } else { s.chars().count() }) as u32
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(600))]
+
+    #[test]
+    fn identity((a, b, _, ascii) in triples()) {
+        prop_assert_eq!(edit_distance(&a, &a, ascii), 0);
+        if a != b {
+            prop_assert!(edit_distance(&a, &b, ascii) > 0);
+        }
+    }
+
+    #[test]
+    fn symmetry((a, b, c, ascii) in triples()) {
+        prop_assert_eq!(edit_distance(&a, &b, ascii), edit_distance(&b, &a, ascii));
+        prop_assert_eq!(edit_distance(&a, &c, ascii), edit_distance(&c, &a, ascii));
+    }
+
+    #[test]
+    fn triangle_inequality((a, b, c, ascii) in triples()) {
+        let ac = edit_distance(&a, &c, ascii);
+        let ab = edit_distance(&a, &b, ascii);
+        let bc = edit_distance(&b, &c, ascii);
+        prop_assert!(ac <= ab + bc);
+    }
+
+    #[test]
+    fn bounded_by_lengths((a, b, _, ascii) in triples()) {
+        let dist = edit_distance(&a, &b, ascii);
+        let (a_len, b_len) = (len(&a, ascii), len(&b, ascii));
+        prop_assert!(dist <= a_len.max(b_len));
+        prop_assert!(dist >= a_len.abs_diff(b_len));
+    }
+}